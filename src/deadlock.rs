@@ -0,0 +1,382 @@
+//! Detecção de deadlock com grafo de espera (wait-for): identifica exatamente
+//! quais processos estão em deadlock e os ciclos de espera entre eles
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use crate::sistema::{AcaoRecurso, EstadoProcesso, PassoRoteiro, Processo, Recurso};
+
+/// Limite de ciclos enumerados por detecção
+pub const MAX_CICLOS: usize = 10;
+
+/// Resultado de uma detecção de deadlock
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deadlock {
+    /// Processos que não conseguem terminar, em ordem crescente de id
+    pub processos: Vec<u32>,
+    /// Cada ciclo é uma lista de (processo, recurso que ele espera); o recurso
+    /// é mantido pelo próximo processo da lista, e o último espera pelo primeiro
+    pub ciclos: Vec<Vec<(u32, Recurso)>>,
+}
+
+impl Deadlock {
+    /// Ciclo formatado como `P1 -[Scanner]-> P2 -[Impressora]-> P1`
+    pub fn descrever_ciclo(ciclo: &[(u32, Recurso)]) -> String {
+        let mut texto = String::new();
+        for (processo, recurso) in ciclo {
+            texto.push_str(&format!("P{} -[{}]-> ", processo, recurso));
+        }
+        if let Some((primeiro, _)) = ciclo.first() {
+            texto.push_str(&format!("P{}", primeiro));
+        }
+        texto
+    }
+}
+
+impl fmt::Display for Deadlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let processos: Vec<String> = self.processos.iter().map(|id| format!("P{}", id)).collect();
+        write!(f, "processos em deadlock: {}", processos.join(", "))?;
+        for ciclo in &self.ciclos {
+            write!(f, "; ciclo: {}", Self::descrever_ciclo(ciclo))?;
+        }
+        Ok(())
+    }
+}
+
+/// Critério de custo para escolher a vítima de um deadlock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriterioVitima {
+    MenorPrioridade,
+    /// Menos trabalho executado: perde-se menos ao encerrar ou reverter
+    MenorProgresso,
+    /// Menor quantidade total de recursos alocados
+    MenosRecursos,
+}
+
+impl fmt::Display for CriterioVitima {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CriterioVitima::MenorPrioridade => write!(f, "menor prioridade"),
+            CriterioVitima::MenorProgresso => write!(f, "menor progresso"),
+            CriterioVitima::MenosRecursos => write!(f, "menos recursos"),
+        }
+    }
+}
+
+/// O que fazer quando um deadlock é detectado
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecuperacaoDeadlock {
+    /// Aborta todos os processos em deadlock
+    EncerrarTodos,
+    /// Aborta uma vítima por vez, a de menor custo, até o deadlock desaparecer
+    EncerrarPorCusto(CriterioVitima),
+    /// Toma os recursos de uma vítima por vez e a faz recomeçar
+    PreemptarERetroceder(CriterioVitima),
+}
+
+impl fmt::Display for RecuperacaoDeadlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecuperacaoDeadlock::EncerrarTodos => write!(f, "Encerrar todos"),
+            RecuperacaoDeadlock::EncerrarPorCusto(criterio) =>
+                write!(f, "Encerrar um por vez ({})", criterio),
+            RecuperacaoDeadlock::PreemptarERetroceder(criterio) =>
+                write!(f, "Preemptar e retroceder ({})", criterio),
+        }
+    }
+}
+
+/// Por que um processo foi abortado
+#[derive(Debug, Clone, PartialEq)]
+pub enum MotivoAborto {
+    /// Encerrado junto com todos os processos do deadlock
+    DeadlockTodos { processos: Vec<u32> },
+    /// Escolhido como vítima pelo critério de custo
+    VitimaDeadlock { criterio: CriterioVitima, processos: Vec<u32> },
+}
+
+impl fmt::Display for MotivoAborto {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let listar = |processos: &[u32]| processos.iter()
+            .map(|id| format!("P{}", id)).collect::<Vec<_>>().join(", ");
+        match self {
+            MotivoAborto::DeadlockTodos { processos } =>
+                write!(f, "deadlock entre {} (todos encerrados)", listar(processos)),
+            MotivoAborto::VitimaDeadlock { criterio, processos } =>
+                write!(f, "vítima do deadlock entre {} (critério: {})", listar(processos), criterio),
+        }
+    }
+}
+
+/// Registro de um processo abortado
+#[derive(Debug, Clone)]
+pub struct ProcessoAbortado {
+    pub processo: Processo,
+    pub tempo: u32,
+    pub motivo: MotivoAborto,
+}
+
+/// O que fazer com um processo que pede recursos fora da ordem global (prevenção)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolacaoOrdem {
+    /// Recusa o processo na admissão
+    Rejeitar,
+    /// Antecipa as aquisições fora de ordem para antes dos recursos de posto maior
+    Reordenar,
+}
+
+impl fmt::Display for ViolacaoOrdem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ViolacaoOrdem::Rejeitar => write!(f, "rejeitar"),
+            ViolacaoOrdem::Reordenar => write!(f, "reordenar"),
+        }
+    }
+}
+
+/// Procura no roteiro uma aquisição feita enquanto o processo possui um recurso
+/// de posto maior ou igual. Retorna (índice do passo que viola a ordem, índice
+/// da aquisição mais antiga ainda mantida que conflita com ele).
+pub fn violacao_de_ordem<F>(roteiro: &[PassoRoteiro], posto: F) -> Option<(usize, usize)>
+where
+    F: Fn(&Recurso) -> usize,
+{
+    // (índice da aquisição, recurso, quantidade ainda mantida)
+    let mut mantidos: Vec<(usize, Recurso, u32)> = Vec::new();
+    for (j, passo) in roteiro.iter().enumerate() {
+        match passo.acao {
+            AcaoRecurso::Adquirir => {
+                let conflito = mantidos.iter()
+                    .find(|&&(_, recurso, quantidade)| quantidade > 0 && posto(&recurso) >= posto(&passo.recurso));
+                if let Some(&(i, _, _)) = conflito {
+                    return Some((j, i));
+                }
+                mantidos.push((j, passo.recurso, passo.quantidade));
+            }
+            AcaoRecurso::Liberar => {
+                let mut restante = passo.quantidade;
+                for (_, recurso, quantidade) in mantidos.iter_mut().rev() {
+                    if *recurso == passo.recurso {
+                        let liberado = restante.min(*quantidade);
+                        *quantidade -= liberado;
+                        restante -= liberado;
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Reescreve o roteiro para adquirir em ordem crescente de posto: cada aquisição
+/// fora de ordem é antecipada para junto da aquisição conflitante (somando-se a
+/// ela se for o mesmo recurso). O processo passa a segurar recursos por mais tempo.
+pub fn reordenar_roteiro<F>(roteiro: &mut Vec<PassoRoteiro>, posto: F)
+where
+    F: Fn(&Recurso) -> usize,
+{
+    while let Some((j, i)) = violacao_de_ordem(roteiro, &posto) {
+        let mut passo = roteiro.remove(j);
+        if roteiro[i].recurso == passo.recurso {
+            roteiro[i].quantidade += passo.quantidade;
+        } else {
+            passo.progresso = roteiro[i].progresso;
+            roteiro.insert(i, passo);
+        }
+    }
+}
+
+/// Escolhe a vítima de menor custo entre os processos em deadlock. Processos
+/// já revertidos mais vezes são poupados primeiro, para evitar starvation.
+pub fn escolher_vitima<'a, I>(processos: I, deadlock: &Deadlock, criterio: CriterioVitima) -> Option<u32>
+where
+    I: Iterator<Item = &'a Processo>,
+{
+    processos
+        .filter(|p| deadlock.processos.contains(&p.id))
+        .min_by_key(|p| {
+            let custo = match criterio {
+                CriterioVitima::MenorPrioridade => p.prioridade as i64,
+                CriterioVitima::MenorProgresso => p.progresso() as i64,
+                CriterioVitima::MenosRecursos => p.recursos_alocados.values().sum::<u32>() as i64,
+            };
+            (p.reversoes, custo, p.id)
+        })
+        .map(|p| p.id)
+}
+
+/// Detecta deadlock entre os processos informados. Só processos bloqueados
+/// têm pedidos pendentes; os demais podem progredir e devolver o que possuem.
+///
+/// Primeiro aplica a redução do grafo de alocação de recursos (que trata
+/// recursos com várias instâncias) para achar o conjunto exato de processos
+/// em deadlock; depois monta o grafo de espera entre eles para listar os ciclos.
+pub fn detectar<'a, I>(processos: I, disponiveis: &HashMap<Recurso, u32>) -> Option<Deadlock>
+where
+    I: Iterator<Item = &'a Processo>,
+{
+    let pedido = |p: &Processo| if p.estado == EstadoProcesso::Bloqueado {
+        p.pedido_em_espera()
+    } else {
+        HashMap::new()
+    };
+
+    let mut trabalho = disponiveis.clone();
+    let mut pendentes: Vec<&Processo> = processos.collect();
+    loop {
+        let posicao = pendentes.iter().position(|p| {
+            pedido(p).iter().all(|(r, &q)| trabalho.get(r).copied().unwrap_or(0) >= q)
+        });
+        match posicao {
+            Some(i) => {
+                let p = pendentes.swap_remove(i);
+                for (recurso, &alocado) in &p.recursos_alocados {
+                    *trabalho.entry(*recurso).or_insert(0) += alocado;
+                }
+            }
+            None => break,
+        }
+    }
+
+    if pendentes.is_empty() {
+        return None;
+    }
+
+    // Grafo de espera: P -> Q quando P pede um recurso que Q (também em deadlock) possui
+    let em_deadlock: BTreeSet<u32> = pendentes.iter().map(|p| p.id).collect();
+    let mut arestas: BTreeMap<u32, Vec<(u32, Recurso)>> = BTreeMap::new();
+    for p in &pendentes {
+        let mut recursos: Vec<Recurso> = pedido(p).into_keys().collect();
+        recursos.sort_by_key(|r| r.to_string());
+        let destinos = arestas.entry(p.id).or_default();
+        for recurso in recursos {
+            for q in &pendentes {
+                let possui = q.recursos_alocados.get(&recurso).is_some_and(|&n| n > 0);
+                if q.id != p.id && possui && !destinos.iter().any(|&(d, _)| d == q.id) {
+                    destinos.push((q.id, recurso));
+                }
+            }
+        }
+        destinos.sort_by_key(|&(d, _)| d);
+    }
+
+    let mut ciclos = Vec::new();
+    for &inicio in &em_deadlock {
+        let mut caminho = Vec::new();
+        buscar_ciclos(inicio, inicio, &arestas, &mut caminho, &mut ciclos);
+    }
+
+    Some(Deadlock { processos: em_deadlock.into_iter().collect(), ciclos })
+}
+
+/// Busca em profundidade por ciclos elementares que começam em `inicio`,
+/// visitando só processos de id maior para não repetir o mesmo ciclo
+fn buscar_ciclos(inicio: u32, atual: u32, arestas: &BTreeMap<u32, Vec<(u32, Recurso)>>,
+                 caminho: &mut Vec<(u32, Recurso)>, ciclos: &mut Vec<Vec<(u32, Recurso)>>) {
+    for &(destino, recurso) in arestas.get(&atual).map(|v| v.as_slice()).unwrap_or(&[]) {
+        if ciclos.len() >= MAX_CICLOS {
+            return;
+        }
+        caminho.push((atual, recurso));
+        if destino == inicio {
+            ciclos.push(caminho.clone());
+        } else if destino > inicio && !caminho.iter().any(|&(p, _)| p == destino) {
+            buscar_ciclos(inicio, destino, arestas, caminho, ciclos);
+        }
+        caminho.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bloqueado(id: u32, possui: &[Recurso], espera: Recurso) -> Processo {
+        let mut processo = Processo::new(id, 5, 1);
+        for recurso in possui {
+            processo.recursos_alocados.insert(*recurso, 1);
+        }
+        processo.pedido_pendente = Some((espera, 1));
+        processo.estado = EstadoProcesso::Bloqueado;
+        processo
+    }
+
+    #[test]
+    fn test_detecta_ciclo_e_processo_dependente() {
+        // P1 e P2 em espera circular; P3 espera pelo disco de P2 sem fazer parte do ciclo
+        let processos = [
+            bloqueado(1, &[Recurso::Impressora], Recurso::Scanner),
+            bloqueado(2, &[Recurso::Scanner, Recurso::Disco], Recurso::Impressora),
+            bloqueado(3, &[], Recurso::Disco),
+            Processo::new(4, 5, 1),
+        ];
+        let disponiveis = HashMap::from([(Recurso::Impressora, 0), (Recurso::Scanner, 0), (Recurso::Disco, 0)]);
+
+        let deadlock = detectar(processos.iter(), &disponiveis).unwrap();
+
+        assert_eq!(deadlock.processos, vec![1, 2, 3]);
+        assert_eq!(deadlock.ciclos, vec![vec![(1, Recurso::Scanner), (2, Recurso::Impressora)]]);
+        assert_eq!(Deadlock::descrever_ciclo(&deadlock.ciclos[0]), "P1 -[Scanner]-> P2 -[Impressora]-> P1");
+    }
+
+    #[test]
+    fn test_sem_deadlock_quando_ha_instancia_livre() {
+        let processos = [
+            bloqueado(1, &[Recurso::Impressora], Recurso::Scanner),
+            bloqueado(2, &[Recurso::Scanner], Recurso::Impressora),
+        ];
+        // Uma segunda impressora livre permite que P2 termine e libere o scanner
+        let disponiveis = HashMap::from([(Recurso::Impressora, 1), (Recurso::Scanner, 0)]);
+
+        assert_eq!(detectar(processos.iter(), &disponiveis), None);
+    }
+
+    #[test]
+    fn test_escolher_vitima_por_criterio() {
+        let mut p1 = bloqueado(1, &[Recurso::Impressora, Recurso::Disco], Recurso::Scanner);
+        p1.prioridade = 3;
+        p1.tempo_restante = 4;
+        let mut p2 = bloqueado(2, &[Recurso::Scanner], Recurso::Impressora);
+        p2.prioridade = 7;
+        p2.tempo_restante = 1;
+        let deadlock = Deadlock { processos: vec![1, 2], ciclos: Vec::new() };
+        let processos = [p1, p2];
+
+        assert_eq!(escolher_vitima(processos.iter(), &deadlock, CriterioVitima::MenorPrioridade), Some(1));
+        assert_eq!(escolher_vitima(processos.iter(), &deadlock, CriterioVitima::MenorProgresso), Some(1));
+        assert_eq!(escolher_vitima(processos.iter(), &deadlock, CriterioVitima::MenosRecursos), Some(2));
+
+        // Quem já foi revertido deixa de ser a vítima preferida
+        let mut processos = processos;
+        processos[0].reversoes = 1;
+        assert_eq!(escolher_vitima(processos.iter(), &deadlock, CriterioVitima::MenorPrioridade), Some(2));
+    }
+
+    #[test]
+    fn test_reordenar_roteiro_por_posto() {
+        let posto = |r: &Recurso| match r {
+            Recurso::Impressora => 0,
+            Recurso::Scanner => 1,
+            _ => 2,
+        };
+        let processo = Processo::new(1, 10, 1)
+            .adquire_em(0, Recurso::Scanner, 1)
+            .adquire_em(3, Recurso::Impressora, 1)
+            .libera_em(5, Recurso::Impressora, 1)
+            .adquire_em(6, Recurso::Scanner, 1);
+        let mut roteiro = processo.roteiro;
+        assert_eq!(violacao_de_ordem(&roteiro, posto), Some((1, 0)));
+
+        reordenar_roteiro(&mut roteiro, posto);
+
+        // A impressora passa a ser pedida antes do scanner, e os dois scanners são somados
+        assert_eq!(violacao_de_ordem(&roteiro, posto), None);
+        let resumo: Vec<(u32, AcaoRecurso, Recurso, u32)> = roteiro.iter()
+            .map(|p| (p.progresso, p.acao, p.recurso, p.quantidade)).collect();
+        assert_eq!(resumo, vec![
+            (0, AcaoRecurso::Adquirir, Recurso::Impressora, 1),
+            (0, AcaoRecurso::Adquirir, Recurso::Scanner, 2),
+            (5, AcaoRecurso::Liberar, Recurso::Impressora, 1),
+        ]);
+    }
+}
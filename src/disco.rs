@@ -0,0 +1,245 @@
+//! Dispositivo de disco: fila de pedidos, posição do cabeçote, tempo de
+//! serviço e algoritmos de escalonamento de disco
+
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Cilindros do disco simulado (0 a 199)
+pub const CILINDROS_DISCO: u32 = 200;
+
+/// Algoritmo que escolhe o próximo pedido atendido pelo disco
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlgoritmoDisco {
+    /// Ordem de chegada
+    Fcfs,
+    /// Menor distância de busca a partir da posição atual
+    Sstf,
+    /// Elevador: segue em um sentido até a borda do disco e então inverte
+    Scan,
+    /// Só atende subindo; ao chegar à borda volta ao cilindro 0
+    CScan,
+    /// Como o SCAN, mas inverte no último pedido em vez de ir até a borda
+    Look,
+}
+
+impl fmt::Display for AlgoritmoDisco {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlgoritmoDisco::Fcfs => write!(f, "FCFS"),
+            AlgoritmoDisco::Sstf => write!(f, "SSTF"),
+            AlgoritmoDisco::Scan => write!(f, "SCAN"),
+            AlgoritmoDisco::CScan => write!(f, "C-SCAN"),
+            AlgoritmoDisco::Look => write!(f, "LOOK"),
+        }
+    }
+}
+
+/// Pedido de E/S de um processo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PedidoDisco {
+    pub processo: u32,
+    pub cilindro: u32,
+    /// Passo em que o pedido entrou na fila
+    pub chegada: u32,
+    /// Passos de transferência, somados ao tempo de busca
+    pub duracao: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Disco {
+    pub algoritmo: AlgoritmoDisco,
+    pub cilindros: u32,
+    /// Cilindro onde está o cabeçote
+    pub posicao: u32,
+    /// Sentido atual do cabeçote (SCAN e LOOK)
+    pub subindo: bool,
+    /// Cilindros percorridos por passo de busca
+    pub velocidade_busca: u32,
+    pub fila: VecDeque<PedidoDisco>,
+    /// Pedido sendo atendido e o passo em que termina
+    pub em_servico: Option<(PedidoDisco, u32)>,
+    pub atendidos: u32,
+    pub distancia_total: u32,
+    /// Soma dos passos que os pedidos esperaram na fila antes do atendimento
+    pub espera_total: u32,
+    pub passos_ocupado: u32,
+}
+
+impl Disco {
+    pub fn new(algoritmo: AlgoritmoDisco, cilindros: u32, posicao: u32) -> Self {
+        Disco {
+            algoritmo,
+            cilindros,
+            posicao: posicao.min(cilindros.saturating_sub(1)),
+            subindo: true,
+            velocidade_busca: 50,
+            fila: VecDeque::new(),
+            em_servico: None,
+            atendidos: 0,
+            distancia_total: 0,
+            espera_total: 0,
+            passos_ocupado: 0,
+        }
+    }
+
+    /// Enfileira o pedido; cilindros além da borda são trazidos para o último cilindro
+    pub fn enfileirar(&mut self, pedido: PedidoDisco) {
+        let mut pedido = pedido;
+        pedido.cilindro = pedido.cilindro.min(self.cilindros.saturating_sub(1));
+        self.fila.push_back(pedido);
+    }
+
+    /// Avança um passo: conclui o pedido em serviço, se terminou, e inicia o
+    /// próximo. Devolve o processo cujo pedido foi concluído.
+    pub fn passo(&mut self, tempo: u32) -> Option<u32> {
+        let concluido = match self.em_servico {
+            Some((pedido, conclusao)) if conclusao <= tempo => {
+                self.em_servico = None;
+                Some(pedido.processo)
+            }
+            _ => None,
+        };
+        if self.em_servico.is_none() {
+            if let Some((pedido, distancia)) = self.escolher() {
+                let busca = distancia.div_ceil(self.velocidade_busca.max(1));
+                let servico = (pedido.duracao + busca).max(1);
+                self.atendidos += 1;
+                self.distancia_total += distancia;
+                self.espera_total += tempo - pedido.chegada;
+                self.em_servico = Some((pedido, tempo + servico - 1));
+            }
+        }
+        if self.em_servico.is_some() {
+            self.passos_ocupado += 1;
+        }
+        concluido
+    }
+
+    /// Retira da fila o próximo pedido segundo o algoritmo e move o cabeçote
+    /// até ele, devolvendo o pedido e a distância percorrida
+    fn escolher(&mut self) -> Option<(PedidoDisco, u32)> {
+        if self.fila.is_empty() {
+            return None;
+        }
+        let borda = self.cilindros.saturating_sub(1);
+        let mut percurso = 0;
+        let indice = match self.algoritmo {
+            AlgoritmoDisco::Fcfs => 0,
+            AlgoritmoDisco::Sstf => self.mais_proximo(|_| true)?,
+            AlgoritmoDisco::Scan | AlgoritmoDisco::Look => {
+                let posicao = self.posicao;
+                let subindo = self.subindo;
+                match self.mais_proximo(|c| if subindo { c >= posicao } else { c <= posicao }) {
+                    Some(indice) => indice,
+                    None => {
+                        // Nada mais neste sentido: o SCAN ainda vai até a borda
+                        if self.algoritmo == AlgoritmoDisco::Scan {
+                            let limite = if subindo { borda } else { 0 };
+                            percurso += limite.abs_diff(self.posicao);
+                            self.posicao = limite;
+                        }
+                        self.subindo = !subindo;
+                        self.mais_proximo(|_| true)?
+                    }
+                }
+            }
+            AlgoritmoDisco::CScan => {
+                let posicao = self.posicao;
+                match self.mais_proximo(|c| c >= posicao) {
+                    Some(indice) => indice,
+                    None => {
+                        // Vai até a borda e retorna ao cilindro 0
+                        percurso += borda.saturating_sub(self.posicao) + borda;
+                        self.posicao = 0;
+                        self.mais_proximo(|_| true)?
+                    }
+                }
+            }
+        };
+        let pedido = self.fila.remove(indice)?;
+        percurso += pedido.cilindro.abs_diff(self.posicao);
+        self.posicao = pedido.cilindro;
+        Some((pedido, percurso))
+    }
+
+    /// Pedido da fila mais próximo do cabeçote entre os cilindros aceitos pelo filtro
+    fn mais_proximo<F: Fn(u32) -> bool>(&self, filtro: F) -> Option<usize> {
+        self.fila.iter().enumerate()
+            .filter(|(_, p)| filtro(p.cilindro))
+            .min_by_key(|&(i, p)| (p.cilindro.abs_diff(self.posicao), i))
+            .map(|(i, _)| i)
+    }
+
+    pub fn utilizacao(&self, tempo_total: u32) -> f64 {
+        self.passos_ocupado as f64 * 100.0 / tempo_total.max(1) as f64
+    }
+
+    pub fn busca_media(&self) -> f64 {
+        self.distancia_total as f64 / self.atendidos.max(1) as f64
+    }
+
+    pub fn espera_media(&self) -> f64 {
+        self.espera_total as f64 / self.atendidos.max(1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fila clássica (Silberschatz) com o cabeçote no cilindro 53, subindo
+    fn distancia_total(algoritmo: AlgoritmoDisco) -> u32 {
+        let mut disco = Disco::new(algoritmo, 200, 53);
+        for (i, &cilindro) in [98, 183, 37, 122, 14, 124, 65, 67].iter().enumerate() {
+            disco.enfileirar(PedidoDisco { processo: i as u32, cilindro, chegada: 0, duracao: 1 });
+        }
+        let mut tempo = 0;
+        while disco.atendidos < 8 || disco.em_servico.is_some() {
+            disco.passo(tempo);
+            tempo += 1;
+        }
+        disco.distancia_total
+    }
+
+    #[test]
+    fn test_distancia_por_algoritmo() {
+        assert_eq!(distancia_total(AlgoritmoDisco::Fcfs), 640);
+        assert_eq!(distancia_total(AlgoritmoDisco::Sstf), 236);
+        assert_eq!(distancia_total(AlgoritmoDisco::Scan), 331);
+        assert_eq!(distancia_total(AlgoritmoDisco::CScan), 382);
+        assert_eq!(distancia_total(AlgoritmoDisco::Look), 299);
+    }
+
+    #[test]
+    fn test_cilindro_fora_do_disco_vai_para_a_borda() {
+        let mut disco = Disco::new(AlgoritmoDisco::CScan, 200, 150);
+        disco.enfileirar(PedidoDisco { processo: 1, cilindro: 500, chegada: 0, duracao: 1 });
+        disco.enfileirar(PedidoDisco { processo: 2, cilindro: 10, chegada: 0, duracao: 1 });
+        assert_eq!(disco.fila[0].cilindro, 199);
+
+        let mut tempo = 0;
+        while disco.atendidos < 2 || disco.em_servico.is_some() {
+            disco.passo(tempo);
+            tempo += 1;
+        }
+        // 150 -> 199, volta ao 0 (199 cilindros) e segue até o 10
+        assert_eq!(disco.distancia_total, 49 + 199 + 10);
+    }
+
+    #[test]
+    fn test_tempo_de_servico_e_espera() {
+        let mut disco = Disco::new(AlgoritmoDisco::Fcfs, 200, 0);
+        disco.enfileirar(PedidoDisco { processo: 1, cilindro: 100, chegada: 0, duracao: 2 });
+        disco.enfileirar(PedidoDisco { processo: 2, cilindro: 100, chegada: 0, duracao: 2 });
+
+        // Busca de 100 cilindros = 2 passos, mais 2 de transferência
+        assert_eq!(disco.passo(0), None);
+        assert_eq!(disco.em_servico.map(|(_, fim)| fim), Some(3));
+        assert_eq!(disco.passo(2), None);
+        assert_eq!(disco.passo(3), Some(1));
+        // O segundo pedido já está no cilindro 100: só a transferência
+        assert_eq!(disco.em_servico.map(|(_, fim)| fim), Some(4));
+        assert_eq!(disco.espera_total, 3);
+        assert_eq!(disco.busca_media(), 50.0);
+    }
+}
@@ -0,0 +1,160 @@
+//! Modelo de energia dos núcleos: estados de potência, níveis de frequência (DVFS)
+//! e governadores que escolhem a frequência conforme a carga
+
+use std::fmt;
+use crate::sistema::ClasseNucleo;
+
+/// Estado de potência de um núcleo em um passo da simulação
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EstadoEnergia {
+    /// Executando um processo (ou despertando para executá-lo)
+    Ativo,
+    /// Sem processo, mas pronto para executar imediatamente
+    Ocioso,
+    /// Sem processo há tempo suficiente para entrar em sono profundo
+    Dormindo,
+}
+
+impl fmt::Display for EstadoEnergia {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EstadoEnergia::Ativo => write!(f, "Ativo"),
+            EstadoEnergia::Ocioso => write!(f, "Ocioso"),
+            EstadoEnergia::Dormindo => write!(f, "Dormindo"),
+        }
+    }
+}
+
+/// Nível de frequência: velocidade em porcentagem da nominal e potência ativa nesse nível
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NivelFrequencia {
+    pub frequencia: u32,
+    pub potencia: f64,
+}
+
+/// Política que escolhe o nível de frequência de cada núcleo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Governador {
+    /// Sempre na frequência máxima (performance)
+    Desempenho,
+    /// Sempre na frequência mínima (powersave)
+    Economia,
+    /// Sobe para a máxima quando a ocupação da janela atinge `limiar_subida` (%)
+    /// e desce um nível quando fica abaixo de `limiar_descida` (%) (ondemand)
+    SobDemanda { limiar_subida: u32, limiar_descida: u32 },
+}
+
+impl fmt::Display for Governador {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Governador::Desempenho => write!(f, "Desempenho (performance)"),
+            Governador::Economia => write!(f, "Economia (powersave)"),
+            Governador::SobDemanda { limiar_subida, limiar_descida } =>
+                write!(f, "Sob demanda (ondemand, sobe em {}%, desce abaixo de {}%)", limiar_subida, limiar_descida),
+        }
+    }
+}
+
+/// Parâmetros de potência de um núcleo. A energia é medida em W × passo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeloEnergia {
+    /// Níveis em ordem crescente de frequência
+    pub niveis: Vec<NivelFrequencia>,
+    pub potencia_ociosa: f64,
+    pub potencia_sono: f64,
+    /// Passos ociosos consecutivos antes de o núcleo entrar em sono
+    pub ociosidade_para_dormir: u32,
+    /// Passos sem progresso ao despachar um processo em um núcleo dormindo
+    pub latencia_despertar: u32,
+    pub governador: Governador,
+}
+
+impl ModeloEnergia {
+    /// Valores típicos para cada classe: núcleos de eficiência gastam bem menos
+    /// em todos os estados, mas acordam mais rápido
+    pub fn padrao(classe: ClasseNucleo, governador: Governador) -> Self {
+        let (niveis, potencia_ociosa, potencia_sono, latencia_despertar) = match classe {
+            ClasseNucleo::Desempenho => (vec![(50, 1.0), (75, 1.8), (100, 3.0)], 0.5, 0.05, 2),
+            ClasseNucleo::Eficiencia => (vec![(50, 0.3), (75, 0.5), (100, 0.8)], 0.15, 0.02, 1),
+        };
+        ModeloEnergia {
+            niveis: niveis.into_iter()
+                .map(|(frequencia, potencia)| NivelFrequencia { frequencia, potencia })
+                .collect(),
+            potencia_ociosa,
+            potencia_sono,
+            ociosidade_para_dormir: 5,
+            latencia_despertar,
+            governador,
+        }
+    }
+
+    pub fn nivel_maximo(&self) -> usize {
+        self.niveis.len().saturating_sub(1)
+    }
+
+    /// Nível em que o núcleo começa a simulação
+    pub fn nivel_inicial(&self) -> usize {
+        match self.governador {
+            Governador::Economia => 0,
+            Governador::Desempenho | Governador::SobDemanda { .. } => self.nivel_maximo(),
+        }
+    }
+
+    /// Próximo nível a partir do atual e da ocupação (%) da última janela
+    pub fn proximo_nivel(&self, nivel_atual: usize, ocupacao: u32) -> usize {
+        match self.governador {
+            Governador::Desempenho => self.nivel_maximo(),
+            Governador::Economia => 0,
+            Governador::SobDemanda { limiar_subida, limiar_descida } => {
+                if ocupacao >= limiar_subida {
+                    self.nivel_maximo()
+                } else if ocupacao < limiar_descida {
+                    nivel_atual.saturating_sub(1)
+                } else {
+                    nivel_atual
+                }
+            }
+        }
+    }
+
+    pub fn frequencia(&self, nivel: usize) -> u32 {
+        self.niveis.get(nivel).map_or(100, |n| n.frequencia)
+    }
+
+    /// Potência consumida em um passo no estado e nível informados
+    pub fn potencia(&self, estado: EstadoEnergia, nivel: usize) -> f64 {
+        match estado {
+            EstadoEnergia::Ativo => self.niveis.get(nivel).map_or(0.0, |n| n.potencia),
+            EstadoEnergia::Ocioso => self.potencia_ociosa,
+            EstadoEnergia::Dormindo => self.potencia_sono,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_governador_sob_demanda() {
+        let modelo = ModeloEnergia::padrao(ClasseNucleo::Desempenho,
+                                           Governador::SobDemanda { limiar_subida: 80, limiar_descida: 30 });
+        assert_eq!(modelo.nivel_inicial(), 2);
+        // Carga baixa desce um nível por janela, carga alta volta direto ao máximo
+        assert_eq!(modelo.proximo_nivel(2, 10), 1);
+        assert_eq!(modelo.proximo_nivel(1, 10), 0);
+        assert_eq!(modelo.proximo_nivel(0, 10), 0);
+        assert_eq!(modelo.proximo_nivel(0, 50), 0);
+        assert_eq!(modelo.proximo_nivel(0, 90), 2);
+    }
+
+    #[test]
+    fn test_potencia_por_estado() {
+        let modelo = ModeloEnergia::padrao(ClasseNucleo::Eficiencia, Governador::Economia);
+        assert_eq!(modelo.nivel_inicial(), 0);
+        assert_eq!(modelo.frequencia(0), 50);
+        assert!(modelo.potencia(EstadoEnergia::Ativo, 0) < modelo.potencia(EstadoEnergia::Ativo, 2));
+        assert!(modelo.potencia(EstadoEnergia::Dormindo, 0) < modelo.potencia(EstadoEnergia::Ocioso, 0));
+    }
+}
@@ -0,0 +1,1027 @@
+//! Políticas de escalonamento plugáveis usadas pelo `Sistema`
+
+use std::collections::{VecDeque, HashMap, BTreeSet};
+use rand::{Rng, SeedableRng};
+use rand::rngs::SmallRng;
+use crate::sistema::Processo;
+
+/// Interface comum a todas as políticas de escalonamento.
+///
+/// O `Sistema` mantém a fila de prontos e consulta a política para decidir
+/// qual processo despachar e quando preemptar o processo em execução. Os
+/// métodos `ao_*` permitem que a política mantenha estado próprio (filas,
+/// contadores, etc.) indexado pelo id do processo.
+pub trait Escalonador {
+    /// Nome exibido nos relatórios
+    fn nome(&self) -> String;
+
+    /// Prioridade considerada pela política (ex.: com envelhecimento); parte
+    /// da prioridade do processo já elevada por herança ou teto de prioridade
+    fn prioridade_efetiva(&self, processo: &Processo) -> i32 {
+        processo.prioridade_efetiva()
+    }
+
+    /// Retorna o índice, em `fila`, do próximo processo a ser despachado
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, tempo: u32) -> Option<usize>;
+
+    /// Chamado a cada passo para o processo em execução, após o decremento de
+    /// `tempo_restante`. Retornar `true` devolve o processo à fila de prontos.
+    fn deve_preemptar(&mut self, _processo: &Processo, _tempo: u32) -> bool {
+        false
+    }
+
+    /// Um novo processo entrou na fila de prontos
+    fn ao_chegar(&mut self, _processo: &Processo, _tempo: u32) {}
+
+    /// Chamado a cada passo, antes da atribuição de processos aos núcleos, com
+    /// a fila de prontos e o processo de cada núcleo (`None` se ocioso).
+    /// Retorna os índices dos núcleos cujos processos devem ser preemptados
+    /// para dar lugar a processos da fila.
+    fn selecionar_preempcoes(&mut self, _fila: &VecDeque<Processo>,
+                             _executando: &[Option<&Processo>], _tempo: u32) -> Vec<usize> {
+        Vec::new()
+    }
+
+    /// O processo escolhido recebeu recursos e começou a executar em um núcleo
+    fn ao_despachar(&mut self, _processo: &Processo, _tempo: u32) {}
+
+    /// Um processo preemptado voltou para a fila de prontos
+    fn ao_preemptar(&mut self, _processo: &Processo, _tempo: u32) {}
+
+    /// Um processo foi concluído
+    fn ao_concluir(&mut self, _processo: &Processo, _tempo: u32) {}
+
+    /// Um processo foi bloqueado por falta de recursos
+    fn ao_bloquear(&mut self, _processo: &Processo, _tempo: u32) {}
+
+    /// Um processo bloqueado obteve recursos e voltou para a fila de prontos
+    fn ao_desbloquear(&mut self, _processo: &Processo, _tempo: u32) {}
+
+    /// Linhas específicas da política exibidas nas estatísticas detalhadas
+    fn relatorio(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Round Robin: ordem de chegada com preempção por quantum
+pub struct EscalonadorRoundRobin {
+    pub quantum: u32,
+}
+
+impl EscalonadorRoundRobin {
+    pub fn new(quantum: u32) -> Self {
+        EscalonadorRoundRobin { quantum }
+    }
+}
+
+impl Escalonador for EscalonadorRoundRobin {
+    fn nome(&self) -> String {
+        "Round Robin".to_string()
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        if fila.is_empty() { None } else { Some(0) }
+    }
+
+    fn deve_preemptar(&mut self, processo: &Processo, _tempo: u32) -> bool {
+        processo.tempo_no_quantum >= self.quantum
+    }
+}
+
+/// Prioridade: maior prioridade efetiva primeiro, não-preemptiva por padrão.
+///
+/// Com `intervalo_envelhecimento` > 0, a prioridade efetiva de um processo
+/// sobe 1 a cada `intervalo_envelhecimento` passos esperando na fila de
+/// prontos, evitando inanição dos processos de baixa prioridade. Na versão
+/// preemptiva, um processo pronto de prioridade maior toma o núcleo de quem
+/// está executando.
+pub struct EscalonadorPrioridade {
+    pub intervalo_envelhecimento: u32,
+    pub preemptivo: bool,
+}
+
+impl EscalonadorPrioridade {
+    pub fn new(intervalo_envelhecimento: u32) -> Self {
+        EscalonadorPrioridade { intervalo_envelhecimento, preemptivo: false }
+    }
+
+    pub fn preemptivo(intervalo_envelhecimento: u32) -> Self {
+        EscalonadorPrioridade { intervalo_envelhecimento, preemptivo: true }
+    }
+}
+
+impl Escalonador for EscalonadorPrioridade {
+    fn nome(&self) -> String {
+        let nome = if self.preemptivo { "Por Prioridade (preemptivo)" } else { "Por Prioridade" };
+        if self.intervalo_envelhecimento > 0 {
+            format!("{} (envelhecimento a cada {} passos)", nome, self.intervalo_envelhecimento)
+        } else {
+            nome.to_string()
+        }
+    }
+
+    fn prioridade_efetiva(&self, processo: &Processo) -> i32 {
+        if self.intervalo_envelhecimento == 0 {
+            return processo.prioridade_efetiva();
+        }
+        processo.prioridade_efetiva() + (processo.tempo_espera / self.intervalo_envelhecimento) as i32
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        fila.iter().enumerate()
+            .max_by_key(|(_, p)| self.prioridade_efetiva(p))
+            .map(|(i, _)| i)
+    }
+
+    fn selecionar_preempcoes(&mut self, fila: &VecDeque<Processo>,
+                             executando: &[Option<&Processo>], _tempo: u32) -> Vec<usize> {
+        if !self.preemptivo {
+            return Vec::new();
+        }
+        let mut na_fila: Vec<i32> = fila.iter().map(|p| self.prioridade_efetiva(p)).collect();
+        na_fila.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut em_execucao: Vec<(i32, usize)> = executando.iter().enumerate()
+            .filter_map(|(i, p)| p.map(|p| (self.prioridade_efetiva(p), i)))
+            .collect();
+        em_execucao.sort_unstable();
+
+        // Núcleos ociosos já recebem os processos da fila sem preempção
+        let ociosos = executando.iter().filter(|p| p.is_none()).count();
+
+        na_fila.iter().skip(ociosos)
+            .zip(em_execucao)
+            .take_while(|&(&alta, (baixa, _))| alta > baixa)
+            .map(|(_, (_, nucleo))| nucleo)
+            .collect()
+    }
+}
+
+/// Shortest Job First não-preemptivo: menor `tempo_restante` primeiro
+pub struct EscalonadorSjf;
+
+impl Escalonador for EscalonadorSjf {
+    fn nome(&self) -> String {
+        "Shortest Job First".to_string()
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        fila.iter().enumerate()
+            .min_by_key(|(_, p)| p.tempo_restante)
+            .map(|(i, _)| i)
+    }
+}
+
+/// Highest Response Ratio Next não-preemptivo: maior razão
+/// (espera + serviço) / serviço primeiro, com espera medida desde `tempo_chegada`.
+pub struct EscalonadorHrrn;
+
+impl EscalonadorHrrn {
+    /// Razão de resposta como fração (numerador, denominador)
+    fn razao(processo: &Processo, tempo: u32) -> (u64, u64) {
+        let servico = processo.tempo_total.max(1) as u64;
+        let espera = tempo.saturating_sub(processo.tempo_chegada) as u64;
+        (espera + servico, servico)
+    }
+}
+
+impl Escalonador for EscalonadorHrrn {
+    fn nome(&self) -> String {
+        "Highest Response Ratio Next".to_string()
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, tempo: u32) -> Option<usize> {
+        // Compara a/b com c/d por multiplicação cruzada; empate mantém a ordem da fila
+        fila.iter().enumerate()
+            .fold(None, |melhor: Option<(usize, (u64, u64))>, (i, p)| {
+                let razao = Self::razao(p, tempo);
+                match melhor {
+                    Some((_, (a, b))) if razao.0 * b <= a * razao.1 => melhor,
+                    _ => Some((i, razao)),
+                }
+            })
+            .map(|(i, _)| i)
+    }
+}
+
+/// Shortest Remaining Time First: versão preemptiva do SJF.
+///
+/// A cada passo, enquanto o processo mais curto da fila tiver `tempo_restante`
+/// menor que o mais longo em execução, o mais longo é preemptado.
+pub struct EscalonadorSrtf;
+
+impl Escalonador for EscalonadorSrtf {
+    fn nome(&self) -> String {
+        "Shortest Remaining Time First".to_string()
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        fila.iter().enumerate()
+            .min_by_key(|(_, p)| p.tempo_restante)
+            .map(|(i, _)| i)
+    }
+
+    fn selecionar_preempcoes(&mut self, fila: &VecDeque<Processo>,
+                             executando: &[Option<&Processo>], _tempo: u32) -> Vec<usize> {
+        let mut na_fila: Vec<u32> = fila.iter().map(|p| p.tempo_restante).collect();
+        na_fila.sort_unstable();
+
+        let mut em_execucao: Vec<(u32, usize)> = executando.iter().enumerate()
+            .filter_map(|(i, p)| p.map(|p| (p.tempo_restante, i)))
+            .collect();
+        em_execucao.sort_unstable_by(|a, b| b.cmp(a));
+
+        // Núcleos ociosos já recebem os processos da fila sem preempção
+        let ociosos = executando.iter().filter(|p| p.is_none()).count();
+
+        na_fila.iter().skip(ociosos)
+            .zip(em_execucao)
+            .take_while(|&(&curto, (longo, _))| curto < longo)
+            .map(|(_, (_, nucleo))| nucleo)
+            .collect()
+    }
+}
+
+/// Multi-Level Feedback Queue: o nível 0 é o mais prioritário.
+///
+/// Processos novos entram no nível 0; quem consome o quantum inteiro do seu
+/// nível desce um nível. A cada `intervalo_boost` passos todos os processos
+/// voltam ao nível 0, evitando inanição dos processos longos.
+pub struct EscalonadorMlfq {
+    pub quantums: Vec<u32>,
+    pub intervalo_boost: u32,
+    niveis: HashMap<u32, usize>,
+    tempo_no_quantum: HashMap<u32, u32>,
+    ultimo_boost: u32,
+}
+
+impl EscalonadorMlfq {
+    /// Cria a MLFQ com um quantum por nível (`quantums[0]` é o nível mais alto)
+    pub fn new(quantums: Vec<u32>, intervalo_boost: u32) -> Self {
+        assert!(!quantums.is_empty(), "MLFQ precisa de pelo menos um nível");
+        EscalonadorMlfq {
+            quantums,
+            intervalo_boost,
+            niveis: HashMap::new(),
+            tempo_no_quantum: HashMap::new(),
+            ultimo_boost: 0,
+        }
+    }
+
+    /// Cria `niveis` níveis com quantum dobrando a cada nível (limitado a `u32::MAX`)
+    pub fn com_niveis(niveis: u32, quantum_base: u32, intervalo_boost: u32) -> Self {
+        let quantums = (0..niveis.max(1))
+            .map(|n| 2u32.checked_pow(n).map_or(u32::MAX, |fator| quantum_base.saturating_mul(fator)))
+            .collect();
+        Self::new(quantums, intervalo_boost)
+    }
+
+    /// Nível atual do processo (0 se desconhecido)
+    pub fn nivel(&self, id: u32) -> usize {
+        self.niveis.get(&id).copied().unwrap_or(0)
+    }
+
+    fn aplicar_boost(&mut self, tempo: u32) {
+        if self.intervalo_boost > 0 && tempo - self.ultimo_boost >= self.intervalo_boost {
+            self.ultimo_boost = tempo;
+            for nivel in self.niveis.values_mut() {
+                *nivel = 0;
+            }
+            self.tempo_no_quantum.clear();
+        }
+    }
+}
+
+impl Escalonador for EscalonadorMlfq {
+    fn nome(&self) -> String {
+        format!("Multi-Level Feedback Queue ({} níveis)", self.quantums.len())
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, tempo: u32) -> Option<usize> {
+        self.aplicar_boost(tempo);
+        // min_by_key devolve o primeiro mínimo: FIFO dentro do mesmo nível
+        fila.iter().enumerate()
+            .min_by_key(|(_, p)| self.nivel(p.id))
+            .map(|(i, _)| i)
+    }
+
+    fn deve_preemptar(&mut self, processo: &Processo, tempo: u32) -> bool {
+        self.aplicar_boost(tempo);
+        let nivel = self.nivel(processo.id);
+        let usado = self.tempo_no_quantum.entry(processo.id).or_insert(0);
+        *usado += 1;
+
+        if *usado < self.quantums[nivel] {
+            return false;
+        }
+
+        // Consumiu o quantum inteiro: desce um nível
+        self.tempo_no_quantum.remove(&processo.id);
+        self.niveis.insert(processo.id, (nivel + 1).min(self.quantums.len() - 1));
+        true
+    }
+
+    fn ao_chegar(&mut self, processo: &Processo, _tempo: u32) {
+        self.niveis.insert(processo.id, 0);
+        self.tempo_no_quantum.remove(&processo.id);
+    }
+
+    fn ao_concluir(&mut self, processo: &Processo, _tempo: u32) {
+        self.niveis.remove(&processo.id);
+        self.tempo_no_quantum.remove(&processo.id);
+    }
+
+    fn ao_bloquear(&mut self, processo: &Processo, _tempo: u32) {
+        self.tempo_no_quantum.remove(&processo.id);
+    }
+}
+
+/// Peso de um processo com prioridade neutra (equivale a nice 0 no Linux)
+pub const PESO_NICE_0: u64 = 1024;
+
+/// Converte `prioridade` em peso no estilo nice: cada nível acima de 5 vale
+/// ~25% a mais de CPU e cada nível abaixo ~25% a menos.
+pub fn peso_por_prioridade(prioridade: i32) -> u64 {
+    let nice = (5 - prioridade).clamp(-20, 19);
+    ((PESO_NICE_0 as f64) / 1.25f64.powi(nice)).round().max(1.0) as u64
+}
+
+/// Completely Fair Scheduler: executa sempre o processo com menor vruntime.
+///
+/// O vruntime avança mais devagar para processos de peso maior. A fatia de
+/// cada processo é a `latencia_alvo` dividida proporcionalmente aos pesos dos
+/// processos executáveis, nunca menor que `granularidade_minima`.
+pub struct EscalonadorCfs {
+    pub latencia_alvo: u32,
+    pub granularidade_minima: u32,
+    vruntime: HashMap<u32, u64>,
+    pesos: HashMap<u32, u64>,
+    prontos: BTreeSet<(u64, u32)>,
+    executado_na_fatia: HashMap<u32, u32>,
+    min_vruntime: u64,
+}
+
+impl EscalonadorCfs {
+    pub fn new(latencia_alvo: u32, granularidade_minima: u32) -> Self {
+        EscalonadorCfs {
+            latencia_alvo,
+            granularidade_minima: granularidade_minima.max(1),
+            vruntime: HashMap::new(),
+            pesos: HashMap::new(),
+            prontos: BTreeSet::new(),
+            executado_na_fatia: HashMap::new(),
+            min_vruntime: 0,
+        }
+    }
+
+    /// Tempo virtual acumulado pelo processo
+    pub fn vruntime(&self, id: u32) -> u64 {
+        self.vruntime.get(&id).copied().unwrap_or(self.min_vruntime)
+    }
+
+    /// Fatia de tempo atual do processo, em passos
+    pub fn fatia(&self, id: u32) -> u32 {
+        let soma: u64 = self.pesos.values().sum();
+        let peso = self.pesos.get(&id).copied().unwrap_or(PESO_NICE_0);
+        if soma == 0 {
+            return self.latencia_alvo.max(self.granularidade_minima);
+        }
+        ((self.latencia_alvo as u64 * peso / soma) as u32).max(self.granularidade_minima)
+    }
+
+    fn enfileirar(&mut self, processo: &Processo, vruntime: u64) {
+        self.vruntime.insert(processo.id, vruntime);
+        self.pesos.insert(processo.id, peso_por_prioridade(processo.prioridade));
+        self.prontos.insert((vruntime, processo.id));
+    }
+
+    fn remover(&mut self, id: u32) {
+        if let Some(&vr) = self.vruntime.get(&id) {
+            self.prontos.remove(&(vr, id));
+        }
+    }
+
+    fn atualizar_min_vruntime(&mut self, candidato: u64) {
+        let menor = self.prontos.iter().next().map_or(candidato, |&(vr, _)| vr.min(candidato));
+        self.min_vruntime = self.min_vruntime.max(menor);
+    }
+}
+
+impl Escalonador for EscalonadorCfs {
+    fn nome(&self) -> String {
+        format!("Completely Fair Scheduler (latência {}, granularidade {})",
+                self.latencia_alvo, self.granularidade_minima)
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        // Processos que entraram na fila sem passar pela política (ex.: troca
+        // de escalonador em tempo de execução) começam em min_vruntime
+        for p in fila {
+            if !self.vruntime.contains_key(&p.id) {
+                self.enfileirar(p, self.min_vruntime);
+            }
+        }
+        self.prontos.iter()
+            .find_map(|&(_, id)| fila.iter().position(|p| p.id == id))
+    }
+
+    fn deve_preemptar(&mut self, processo: &Processo, _tempo: u32) -> bool {
+        let peso = self.pesos.get(&processo.id).copied().unwrap_or(PESO_NICE_0);
+        let vr = self.vruntime(processo.id) + PESO_NICE_0 * PESO_NICE_0 / peso;
+        self.vruntime.insert(processo.id, vr);
+        self.atualizar_min_vruntime(vr);
+        *self.executado_na_fatia.entry(processo.id).or_insert(0) += 1;
+
+        // A decisão depende da fila que disputa o núcleo: ver selecionar_preempcoes
+        false
+    }
+
+    fn selecionar_preempcoes(&mut self, fila: &VecDeque<Processo>,
+                             executando: &[Option<&Processo>], _tempo: u32) -> Vec<usize> {
+        // Só vale a pena preemptar se houver alguém esperando além do que os
+        // núcleos ociosos já vão absorver
+        let ociosos = executando.iter().filter(|p| p.is_none()).count();
+        let esperando = fila.len().saturating_sub(ociosos);
+
+        let mut vencidos: Vec<(u64, usize)> = executando.iter().enumerate()
+            .filter_map(|(i, p)| p.map(|p| (p.id, i)))
+            .filter(|&(id, _)| self.executado_na_fatia.get(&id).copied().unwrap_or(0) >= self.fatia(id))
+            .map(|(id, i)| (self.vruntime(id), i))
+            .collect();
+        vencidos.sort_unstable_by(|a, b| b.cmp(a));
+
+        vencidos.into_iter().take(esperando).map(|(_, i)| i).collect()
+    }
+
+    fn ao_chegar(&mut self, processo: &Processo, _tempo: u32) {
+        let vr = self.min_vruntime;
+        self.enfileirar(processo, vr);
+    }
+
+    fn ao_despachar(&mut self, processo: &Processo, _tempo: u32) {
+        self.remover(processo.id);
+        self.executado_na_fatia.insert(processo.id, 0);
+    }
+
+    fn ao_preemptar(&mut self, processo: &Processo, _tempo: u32) {
+        let vr = self.vruntime(processo.id);
+        self.enfileirar(processo, vr);
+    }
+
+    fn ao_concluir(&mut self, processo: &Processo, _tempo: u32) {
+        self.remover(processo.id);
+        self.vruntime.remove(&processo.id);
+        self.pesos.remove(&processo.id);
+        self.executado_na_fatia.remove(&processo.id);
+    }
+
+    fn ao_bloquear(&mut self, processo: &Processo, _tempo: u32) {
+        self.remover(processo.id);
+        self.pesos.remove(&processo.id);
+    }
+
+    fn ao_desbloquear(&mut self, processo: &Processo, _tempo: u32) {
+        // Quem dormiu não acumula crédito: volta no mínimo em min_vruntime
+        let vr = self.vruntime(processo.id).max(self.min_vruntime);
+        self.enfileirar(processo, vr);
+    }
+}
+
+/// Critério de prioridade das políticas de tempo real
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CriterioTempoReal {
+    /// Earliest Deadline First: menor deadline absoluto primeiro
+    Edf,
+    /// Rate Monotonic: menor período primeiro (prioridade estática)
+    RateMonotonic,
+}
+
+/// EDF e Rate Monotonic preemptivos.
+///
+/// Processos sem deadline/período ficam atrás de todas as tarefas de tempo
+/// real. A cada passo, enquanto o processo da fila com menor chave tiver chave
+/// menor que o de maior chave em execução, este é preemptado.
+pub struct EscalonadorTempoReal {
+    pub criterio: CriterioTempoReal,
+}
+
+impl EscalonadorTempoReal {
+    pub fn new(criterio: CriterioTempoReal) -> Self {
+        EscalonadorTempoReal { criterio }
+    }
+
+    pub fn edf() -> Self {
+        Self::new(CriterioTempoReal::Edf)
+    }
+
+    pub fn rate_monotonic() -> Self {
+        Self::new(CriterioTempoReal::RateMonotonic)
+    }
+
+    fn chave(&self, processo: &Processo) -> u32 {
+        match self.criterio {
+            CriterioTempoReal::Edf => processo.deadline,
+            CriterioTempoReal::RateMonotonic => processo.periodo,
+        }.unwrap_or(u32::MAX)
+    }
+}
+
+impl Escalonador for EscalonadorTempoReal {
+    fn nome(&self) -> String {
+        match self.criterio {
+            CriterioTempoReal::Edf => "Earliest Deadline First".to_string(),
+            CriterioTempoReal::RateMonotonic => "Rate Monotonic".to_string(),
+        }
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        fila.iter().enumerate()
+            .min_by_key(|(_, p)| self.chave(p))
+            .map(|(i, _)| i)
+    }
+
+    fn selecionar_preempcoes(&mut self, fila: &VecDeque<Processo>,
+                             executando: &[Option<&Processo>], _tempo: u32) -> Vec<usize> {
+        let mut na_fila: Vec<u32> = fila.iter().map(|p| self.chave(p)).collect();
+        na_fila.sort_unstable();
+
+        let mut em_execucao: Vec<(u32, usize)> = executando.iter().enumerate()
+            .filter_map(|(i, p)| p.map(|p| (self.chave(p), i)))
+            .collect();
+        em_execucao.sort_unstable_by(|a, b| b.cmp(a));
+
+        // Núcleos ociosos absorvem os primeiros da fila sem preempção; cada
+        // processo restante só desloca quem tem chave maior que a dele
+        let ociosos = executando.iter().filter(|p| p.is_none()).count();
+        na_fila.into_iter().skip(ociosos)
+            .zip(em_execucao)
+            .take_while(|&(esperando, (em_nucleo, _))| esperando < em_nucleo)
+            .map(|(_, (_, nucleo))| nucleo)
+            .collect()
+    }
+}
+
+/// Bilhetes por unidade de prioridade nas políticas de compartilhamento proporcional
+pub const BILHETES_POR_PRIORIDADE: u32 = 100;
+
+/// Constante dividida pelos bilhetes para obter o passo (stride) de cada processo
+pub const STRIDE_GRANDE: u64 = 1 << 20;
+
+/// Converte `prioridade` em bilhetes: prioridades menores que 1 valem um
+/// nível, e prioridades muito altas saturam em `u32::MAX`.
+pub fn bilhetes_por_prioridade(prioridade: i32) -> u32 {
+    (prioridade.max(1) as u32).saturating_mul(BILHETES_POR_PRIORIDADE)
+}
+
+/// Fração da CPU recebida por um processo comparada à sua fração dos bilhetes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticipacaoCpu {
+    pub id: u32,
+    pub bilhetes: u32,
+    pub tempo_cpu: u32,
+    pub fracao_bilhetes: f64,
+    pub fracao_cpu: f64,
+}
+
+/// Contabilidade comum às políticas de loteria e stride
+#[derive(Default)]
+struct Contabilidade {
+    bilhetes: HashMap<u32, u32>,
+    tempo_cpu: HashMap<u32, u32>,
+    executado_no_quantum: HashMap<u32, u32>,
+}
+
+impl Contabilidade {
+    fn registrar(&mut self, processo: &Processo) {
+        self.bilhetes.entry(processo.id).or_insert_with(|| bilhetes_por_prioridade(processo.prioridade));
+    }
+
+    /// Contabiliza um passo de CPU e retorna quanto já executou no quantum atual
+    fn executar_passo(&mut self, processo: &Processo) -> u32 {
+        self.registrar(processo);
+        *self.tempo_cpu.entry(processo.id).or_insert(0) += 1;
+        let executado = self.executado_no_quantum.entry(processo.id).or_insert(0);
+        *executado += 1;
+        *executado
+    }
+
+    fn participacao(&self) -> Vec<ParticipacaoCpu> {
+        let total_bilhetes: u32 = self.bilhetes.values().sum();
+        let total_cpu: u32 = self.tempo_cpu.values().sum();
+        let mut participacao: Vec<ParticipacaoCpu> = self.bilhetes.iter()
+            .map(|(&id, &bilhetes)| {
+                let tempo_cpu = self.tempo_cpu.get(&id).copied().unwrap_or(0);
+                ParticipacaoCpu {
+                    id,
+                    bilhetes,
+                    tempo_cpu,
+                    fracao_bilhetes: bilhetes as f64 / total_bilhetes.max(1) as f64,
+                    fracao_cpu: tempo_cpu as f64 / total_cpu.max(1) as f64,
+                }
+            })
+            .collect();
+        participacao.sort_by_key(|p| p.id);
+        participacao
+    }
+
+    fn relatorio(&self) -> Vec<String> {
+        let mut linhas = vec!["Participação na CPU (recebida vs. bilhetes):".to_string()];
+        for p in self.participacao() {
+            linhas.push(format!("  P{}: {} bilhetes ({:.1}%), {} passos de CPU ({:.1}%)",
+                                p.id, p.bilhetes, p.fracao_bilhetes * 100.0,
+                                p.tempo_cpu, p.fracao_cpu * 100.0));
+        }
+        linhas
+    }
+}
+
+/// Escalonamento por loteria: cada processo recebe bilhetes proporcionais à
+/// prioridade e o próximo a executar é sorteado entre os bilhetes da fila.
+pub struct EscalonadorLoteria {
+    pub quantum: u32,
+    rng: SmallRng,
+    contabilidade: Contabilidade,
+}
+
+impl EscalonadorLoteria {
+    pub fn new(quantum: u32, semente: u64) -> Self {
+        EscalonadorLoteria {
+            quantum,
+            rng: SmallRng::seed_from_u64(semente),
+            contabilidade: Contabilidade::default(),
+        }
+    }
+
+    pub fn participacao(&self) -> Vec<ParticipacaoCpu> {
+        self.contabilidade.participacao()
+    }
+}
+
+impl Escalonador for EscalonadorLoteria {
+    fn nome(&self) -> String {
+        "Loteria".to_string()
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        let total: u32 = fila.iter().map(|p| bilhetes_por_prioridade(p.prioridade)).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut sorteado = self.rng.gen_range(0..total);
+        for (i, p) in fila.iter().enumerate() {
+            let bilhetes = bilhetes_por_prioridade(p.prioridade);
+            if sorteado < bilhetes {
+                return Some(i);
+            }
+            sorteado -= bilhetes;
+        }
+        None
+    }
+
+    fn deve_preemptar(&mut self, processo: &Processo, _tempo: u32) -> bool {
+        self.contabilidade.executar_passo(processo) >= self.quantum
+    }
+
+    fn ao_chegar(&mut self, processo: &Processo, _tempo: u32) {
+        self.contabilidade.registrar(processo);
+    }
+
+    fn ao_despachar(&mut self, processo: &Processo, _tempo: u32) {
+        self.contabilidade.executado_no_quantum.insert(processo.id, 0);
+    }
+
+    fn relatorio(&self) -> Vec<String> {
+        self.contabilidade.relatorio()
+    }
+}
+
+/// Stride scheduling: versão determinística da loteria. Cada processo avança
+/// seu `pass` em `STRIDE_GRANDE / bilhetes` por passo executado e o menor
+/// `pass` executa em seguida.
+pub struct EscalonadorStride {
+    pub quantum: u32,
+    passes: HashMap<u32, u64>,
+    contabilidade: Contabilidade,
+}
+
+impl EscalonadorStride {
+    pub fn new(quantum: u32) -> Self {
+        EscalonadorStride {
+            quantum,
+            passes: HashMap::new(),
+            contabilidade: Contabilidade::default(),
+        }
+    }
+
+    pub fn participacao(&self) -> Vec<ParticipacaoCpu> {
+        self.contabilidade.participacao()
+    }
+
+    /// Processos que chegam começam no menor pass atual para não monopolizar a CPU
+    fn pass_minimo(&self) -> u64 {
+        self.passes.values().copied().min().unwrap_or(0)
+    }
+
+    fn pass(&self, id: u32) -> u64 {
+        self.passes.get(&id).copied().unwrap_or_else(|| self.pass_minimo())
+    }
+}
+
+impl Escalonador for EscalonadorStride {
+    fn nome(&self) -> String {
+        "Stride".to_string()
+    }
+
+    fn escolher_proximo(&mut self, fila: &VecDeque<Processo>, _tempo: u32) -> Option<usize> {
+        fila.iter().enumerate()
+            .min_by_key(|(_, p)| self.pass(p.id))
+            .map(|(i, _)| i)
+    }
+
+    fn deve_preemptar(&mut self, processo: &Processo, _tempo: u32) -> bool {
+        let stride = STRIDE_GRANDE / bilhetes_por_prioridade(processo.prioridade) as u64;
+        let pass = self.pass(processo.id) + stride;
+        self.passes.insert(processo.id, pass);
+        self.contabilidade.executar_passo(processo) >= self.quantum
+    }
+
+    fn ao_chegar(&mut self, processo: &Processo, _tempo: u32) {
+        let pass = self.pass_minimo();
+        self.passes.insert(processo.id, pass);
+        self.contabilidade.registrar(processo);
+    }
+
+    fn ao_despachar(&mut self, processo: &Processo, _tempo: u32) {
+        self.contabilidade.executado_no_quantum.insert(processo.id, 0);
+    }
+
+    fn ao_concluir(&mut self, processo: &Processo, _tempo: u32) {
+        self.passes.remove(&processo.id);
+    }
+
+    fn relatorio(&self) -> Vec<String> {
+        self.contabilidade.relatorio()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fila_exemplo() -> VecDeque<Processo> {
+        VecDeque::from(vec![
+            Processo::new(1, 30, 1),
+            Processo::new(2, 20, 5),
+            Processo::new(3, 40, 3),
+        ])
+    }
+
+    #[test]
+    fn test_round_robin_escolhe_inicio_da_fila() {
+        let mut escalonador = EscalonadorRoundRobin::new(2);
+        assert_eq!(escalonador.escolher_proximo(&fila_exemplo(), 0), Some(0));
+        assert_eq!(escalonador.escolher_proximo(&VecDeque::new(), 0), None);
+    }
+
+    #[test]
+    fn test_round_robin_preempta_no_quantum() {
+        let mut escalonador = EscalonadorRoundRobin::new(2);
+        let mut processo = Processo::new(1, 10, 1).com_chegada(5);
+
+        processo.tempo_no_quantum = 1;
+        assert!(!escalonador.deve_preemptar(&processo, 6));
+        processo.tempo_no_quantum = 2;
+        assert!(escalonador.deve_preemptar(&processo, 7));
+    }
+
+    #[test]
+    fn test_prioridade_e_sjf_retornam_indice() {
+        let fila = fila_exemplo();
+        assert_eq!(EscalonadorPrioridade::new(0).escolher_proximo(&fila, 0), Some(1));
+        assert_eq!(EscalonadorSjf.escolher_proximo(&fila, 0), Some(1));
+        assert!(!EscalonadorSjf.deve_preemptar(&fila[0], 4));
+    }
+
+    #[test]
+    fn test_mlfq_rebaixa_ao_consumir_quantum() {
+        let mut escalonador = EscalonadorMlfq::com_niveis(3, 2, 0);
+        assert_eq!(escalonador.quantums, vec![2, 4, 8]);
+
+        let fila = fila_exemplo();
+        for p in &fila {
+            escalonador.ao_chegar(p, 0);
+        }
+
+        assert!(!escalonador.deve_preemptar(&fila[0], 1));
+        assert!(escalonador.deve_preemptar(&fila[0], 2));
+        assert_eq!(escalonador.nivel(1), 1);
+
+        // P1 desceu de nível, então P2 (nível 0) passa à frente
+        assert_eq!(escalonador.escolher_proximo(&fila, 3), Some(1));
+    }
+
+    #[test]
+    fn test_mlfq_com_muitos_niveis_satura_quantum() {
+        let escalonador = EscalonadorMlfq::com_niveis(40, 3, 50);
+        assert_eq!(escalonador.quantums.len(), 40);
+        assert_eq!(escalonador.quantums[2], 12);
+        assert_eq!(escalonador.quantums[39], u32::MAX);
+    }
+
+    #[test]
+    fn test_mlfq_boost_devolve_ao_nivel_zero() {
+        let mut escalonador = EscalonadorMlfq::com_niveis(2, 1, 10);
+        let processo = Processo::new(1, 50, 1);
+        escalonador.ao_chegar(&processo, 0);
+
+        assert!(escalonador.deve_preemptar(&processo, 1));
+        assert!(!escalonador.deve_preemptar(&processo, 2));
+        assert!(escalonador.deve_preemptar(&processo, 3));
+        assert_eq!(escalonador.nivel(1), 1); // já está no último nível
+
+        escalonador.escolher_proximo(&VecDeque::from(vec![processo]), 10);
+        assert_eq!(escalonador.nivel(1), 0);
+    }
+
+    #[test]
+    fn test_peso_por_prioridade() {
+        assert_eq!(peso_por_prioridade(5), PESO_NICE_0);
+        assert!(peso_por_prioridade(9) > peso_por_prioridade(5));
+        assert!(peso_por_prioridade(1) < peso_por_prioridade(5));
+    }
+
+    #[test]
+    fn test_cfs_escolhe_menor_vruntime() {
+        let mut escalonador = EscalonadorCfs::new(20, 2);
+        let fila = fila_exemplo();
+        for p in &fila {
+            escalonador.ao_chegar(p, 0);
+        }
+
+        // P1 executa uma fatia e volta com vruntime maior que os demais
+        escalonador.ao_despachar(&fila[0], 0);
+        let esperando: VecDeque<Processo> = fila.iter().skip(1).cloned().collect();
+        let mut passos = 0;
+        loop {
+            escalonador.deve_preemptar(&fila[0], passos);
+            passos += 1;
+            if escalonador.selecionar_preempcoes(&esperando, &[Some(&fila[0])], passos) == vec![0] {
+                break;
+            }
+        }
+        escalonador.ao_preemptar(&fila[0], passos);
+
+        assert!(escalonador.vruntime(1) > escalonador.vruntime(2));
+        assert_eq!(escalonador.escolher_proximo(&fila, passos), Some(1));
+    }
+
+    #[test]
+    fn test_cfs_nao_preempta_sem_ninguem_esperando() {
+        let mut escalonador = EscalonadorCfs::new(4, 1);
+        let sozinho = Processo::new(1, 20, 5);
+        let outro = Processo::new(2, 20, 5);
+        escalonador.ao_chegar(&sozinho, 0);
+        escalonador.ao_chegar(&outro, 0);
+        escalonador.ao_despachar(&sozinho, 0);
+        for passo in 0..10 {
+            escalonador.deve_preemptar(&sozinho, passo);
+        }
+
+        // A fatia venceu, mas a fila que disputa este núcleo está vazia; a
+        // fila global da política (com P2) não conta
+        assert!(escalonador.selecionar_preempcoes(&VecDeque::new(), &[Some(&sozinho)], 10).is_empty());
+        assert_eq!(escalonador.selecionar_preempcoes(&VecDeque::from(vec![outro.clone()]), &[Some(&sozinho)], 10), vec![0]);
+        // Um núcleo ocioso absorve quem espera sem preempção
+        assert!(escalonador.selecionar_preempcoes(&VecDeque::from(vec![outro]), &[Some(&sozinho), None], 10).is_empty());
+    }
+
+    #[test]
+    fn test_cfs_fatia_proporcional_ao_peso() {
+        let mut escalonador = EscalonadorCfs::new(30, 1);
+        let alta = Processo::new(1, 10, 9);
+        let baixa = Processo::new(2, 10, 1);
+        escalonador.ao_chegar(&alta, 0);
+        escalonador.ao_chegar(&baixa, 0);
+
+        assert!(escalonador.fatia(1) > escalonador.fatia(2));
+        assert!(escalonador.fatia(2) >= escalonador.granularidade_minima);
+    }
+
+    #[test]
+    fn test_edf_escolhe_menor_deadline() {
+        let mut escalonador = EscalonadorTempoReal::edf();
+        let mut fila = fila_exemplo();
+        fila[0].deadline = Some(30);
+        fila[2].deadline = Some(10);
+
+        // P2 não tem deadline e fica por último
+        assert_eq!(escalonador.escolher_proximo(&fila, 0), Some(2));
+    }
+
+    #[test]
+    fn test_rate_monotonic_preempta_periodo_menor() {
+        let mut escalonador = EscalonadorTempoReal::rate_monotonic();
+        let longa = Processo::new(1, 10, 1).periodico(50);
+        let curta = Processo::new(2, 2, 1).periodico(5);
+
+        assert!(escalonador.selecionar_preempcoes(&VecDeque::new(), &[Some(&longa)], 1).is_empty());
+        let fila = VecDeque::from(vec![curta]);
+        assert_eq!(escalonador.selecionar_preempcoes(&fila, &[Some(&longa)], 2), vec![0]);
+        // Com um núcleo ocioso a tarefa curta não precisa deslocar ninguém
+        assert!(escalonador.selecionar_preempcoes(&fila, &[Some(&longa), None], 2).is_empty());
+    }
+
+    #[test]
+    fn test_srtf_preempta_o_mais_longo() {
+        let mut escalonador = EscalonadorSrtf;
+        let fila = VecDeque::from(vec![Processo::new(4, 2, 1), Processo::new(5, 25, 1)]);
+        let longo = Processo::new(1, 30, 1);
+        let medio = Processo::new(2, 10, 1);
+
+        let preempcoes = escalonador.selecionar_preempcoes(&fila, &[Some(&medio), Some(&longo)], 0);
+        assert_eq!(preempcoes, vec![1]);
+
+        // Com um núcleo ocioso, o processo curto não precisa de preempção
+        let preempcoes = escalonador.selecionar_preempcoes(&fila, &[Some(&medio), None], 0);
+        assert!(preempcoes.is_empty());
+    }
+
+    #[test]
+    fn test_prioridade_com_envelhecimento() {
+        let mut escalonador = EscalonadorPrioridade::new(5);
+        let mut fila = fila_exemplo();
+        fila[0].tempo_espera = 25; // prioridade 1 + 25/5 = 6
+
+        assert_eq!(escalonador.prioridade_efetiva(&fila[0]), 6);
+        assert_eq!(escalonador.escolher_proximo(&fila, 0), Some(0));
+    }
+
+    fn simular_cpu(escalonador: &mut dyn Escalonador, fila: &mut VecDeque<Processo>, passos: u32) {
+        for p in fila.iter() {
+            escalonador.ao_chegar(p, 0);
+        }
+        let mut tempo = 0;
+        while tempo < passos {
+            let indice = escalonador.escolher_proximo(fila, tempo).unwrap();
+            let processo = fila.remove(indice).unwrap();
+            escalonador.ao_despachar(&processo, tempo);
+            loop {
+                tempo += 1;
+                if escalonador.deve_preemptar(&processo, tempo) {
+                    break;
+                }
+            }
+            fila.push_back(processo);
+        }
+    }
+
+    #[test]
+    fn test_bilhetes_por_prioridade() {
+        assert_eq!(bilhetes_por_prioridade(3), 3 * BILHETES_POR_PRIORIDADE);
+        assert_eq!(bilhetes_por_prioridade(-2), BILHETES_POR_PRIORIDADE);
+        assert_eq!(bilhetes_por_prioridade(i32::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn test_stride_proporcional_aos_bilhetes() {
+        let mut escalonador = EscalonadorStride::new(1);
+        let mut fila = VecDeque::from(vec![Processo::new(1, 1000, 3), Processo::new(2, 1000, 1)]);
+        simular_cpu(&mut escalonador, &mut fila, 400);
+
+        let participacao = escalonador.participacao();
+        assert_eq!(participacao[0].tempo_cpu, 300);
+        assert_eq!(participacao[1].tempo_cpu, 100);
+        assert!((participacao[0].fracao_bilhetes - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_loteria_deterministica_com_semente() {
+        let mut a = EscalonadorLoteria::new(1, 7);
+        let mut b = EscalonadorLoteria::new(1, 7);
+        let mut fila_a = VecDeque::from(vec![Processo::new(1, 1000, 4), Processo::new(2, 1000, 1)]);
+        let mut fila_b = fila_a.clone();
+        simular_cpu(&mut a, &mut fila_a, 1000);
+        simular_cpu(&mut b, &mut fila_b, 1000);
+
+        assert_eq!(a.participacao(), b.participacao());
+        // Com 80% dos bilhetes, P1 deve receber aproximadamente 80% da CPU
+        assert!((a.participacao()[0].fracao_cpu - 0.8).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_hrrn_favorece_quem_espera_mais() {
+        let mut escalonador = EscalonadorHrrn;
+        let mut fila = VecDeque::from(vec![Processo::new(1, 10, 1), Processo::new(2, 2, 1)]);
+
+        // No início ambos têm razão 1: vale a ordem da fila
+        assert_eq!(escalonador.escolher_proximo(&fila, 0), Some(0));
+
+        // T=4: P1 = (4+10)/10 = 1.4, P2 = (4+2)/2 = 3
+        assert_eq!(escalonador.escolher_proximo(&fila, 4), Some(1));
+
+        // P1 chegou bem antes: (40+10)/10 = 5 contra (4+2)/2 = 3
+        fila[1].tempo_chegada = 36;
+        assert_eq!(escalonador.escolher_proximo(&fila, 40), Some(0));
+    }
+}
@@ -1,6 +1,13 @@
 //! Módulo com exemplos pré-configurados para testar o sistema
 
-use crate::sistema::{Sistema, Processo, Recurso, AlgoritmoEscalonamento};
+use crate::sistema::{
+    Sistema, Processo, Recurso, Rajada, AlgoritmoEscalonamento, ClasseNucleo, PosicionamentoEnergia,
+    MEMORIA_TOTAL_MB,
+};
+use crate::memoria::PoliticaSubstituicao;
+use crate::disco::AlgoritmoDisco;
+use crate::sincronizacao::ProtocoloPrioridade;
+use crate::escalonador::EscalonadorPrioridade;
 use rand::Rng;
 use rand::thread_rng;
 
@@ -8,7 +15,7 @@ pub fn exemplo_round_robin() -> Sistema {
     let mut sistema = Sistema::new(2, 3, AlgoritmoEscalonamento::RoundRobin);
 
     for i in 1..=4 {
-        sistema.adicionar_processo(Processo::new(i, i * 2, 1).necessita_recurso(Recurso::Memoria(1024), 1));
+        sistema.adicionar_processo(Processo::new(i, i * 2, 1).necessita_recurso(Recurso::Memoria, 1024));
     }
 
     sistema
@@ -19,7 +26,7 @@ pub fn exemplo_prioridade() -> Sistema {
 
     let prioridades = [3, 1, 4, 2];
     for (i, &pri) in prioridades.iter().enumerate() {
-        sistema.adicionar_processo(Processo::new(i as u32 + 1, 4, pri).necessita_recurso(Recurso::Memoria(1024), 1));
+        sistema.adicionar_processo(Processo::new(i as u32 + 1, 4, pri).necessita_recurso(Recurso::Memoria, 1024));
     }
 
     sistema
@@ -33,20 +40,21 @@ pub fn exemplo_deadlock() -> Sistema {
     sistema.recursos_disponiveis.insert(Recurso::Impressora, 1);
     sistema.recursos_disponiveis.insert(Recurso::Scanner, 1);
     sistema.recursos_disponiveis.insert(Recurso::Disco, 3);
-    sistema.recursos_disponiveis.insert(Recurso::Memoria(1024), 8);
+    sistema.recursos_disponiveis.insert(Recurso::Memoria, MEMORIA_TOTAL_MB);
 
-    // Processo 1: precisa de impressora e depois scanner
+    // Processo 1: pega a impressora no início e o scanner após 2 unidades
     sistema.adicionar_processo(
         Processo::new(1, 5, 1)
-            .necessita_recurso(Recurso::Impressora, 1)
-            .necessita_recurso(Recurso::Scanner, 1)
+            .adquire_em(0, Recurso::Impressora, 1)
+            .adquire_em(2, Recurso::Scanner, 1)
     );
 
-    // Processo 2: precisa de scanner e depois impressora
+    // Processo 2: pega o scanner no início e a impressora após 2 unidades,
+    // formando uma espera circular com o processo 1
     sistema.adicionar_processo(
         Processo::new(2, 5, 1)
-            .necessita_recurso(Recurso::Scanner, 1)
-            .necessita_recurso(Recurso::Impressora, 1)
+            .adquire_em(0, Recurso::Scanner, 1)
+            .adquire_em(2, Recurso::Impressora, 1)
     );
 
     sistema
@@ -61,7 +69,7 @@ pub fn exemplo_multinucleo() -> Sistema {
         let tempo = rng.gen_range(1..10);
         sistema.adicionar_processo(
             Processo::new(i, tempo, 1)
-                .necessita_recurso(Recurso::Memoria(1024), 1)
+                .necessita_recurso(Recurso::Memoria, 1024)
         );
     }
 
@@ -75,26 +83,207 @@ pub fn exemplo_complexo() -> Sistema {
     // Processos com diferentes necessidades de recursos
     sistema.adicionar_processo(
         Processo::new(1, 6, 2)
-            .necessita_recurso(Recurso::Memoria(1024), 2)
+            .necessita_recurso(Recurso::Memoria, 2048)
             .necessita_recurso(Recurso::Disco, 1)
     );
 
     sistema.adicionar_processo(
         Processo::new(2, 4, 1)
-            .necessita_recurso(Recurso::Memoria(1024), 1)
+            .necessita_recurso(Recurso::Memoria, 1024)
             .necessita_recurso(Recurso::Impressora, 1)
     );
 
     sistema.adicionar_processo(
         Processo::new(3, 8, 3)
-            .necessita_recurso(Recurso::Memoria(1024), 4)
+            .necessita_recurso(Recurso::Memoria, 4096)
             .necessita_recurso(Recurso::Scanner, 1)
     );
 
     sistema.adicionar_processo(
         Processo::new(4, 3, 2)
-            .necessita_recurso(Recurso::Memoria(1024), 1)
+            .necessita_recurso(Recurso::Memoria, 1024)
     );
 
     sistema
-}
\ No newline at end of file
+}
+
+/// Exemplo de tarefas periódicas de tempo real escalonadas por EDF
+pub fn exemplo_tempo_real() -> Sistema {
+    let mut sistema = Sistema::new(1, 1, AlgoritmoEscalonamento::Edf);
+
+    // Utilização total = 1/4 + 2/8 + 3/12 = 0.75, escalonável por EDF
+    sistema.adicionar_processo(Processo::new(1, 1, 1).periodico(4));
+    sistema.adicionar_processo(Processo::new(2, 2, 1).periodico(8));
+    sistema.adicionar_processo(Processo::new(3, 3, 1).periodico(12));
+
+    sistema
+}
+
+/// Exemplo big.LITTLE: 2 núcleos de desempenho (2x) e 4 de eficiência (0,5x)
+pub fn exemplo_big_little() -> Sistema {
+    let topologia = [
+        (ClasseNucleo::Desempenho, 200),
+        (ClasseNucleo::Desempenho, 200),
+        (ClasseNucleo::Eficiencia, 50),
+        (ClasseNucleo::Eficiencia, 50),
+        (ClasseNucleo::Eficiencia, 50),
+        (ClasseNucleo::Eficiencia, 50),
+    ];
+    let mut sistema = Sistema::com_topologia(&topologia, 3, AlgoritmoEscalonamento::RoundRobin);
+    sistema.posicionamento_energia = Some(PosicionamentoEnergia {
+        limiar_tempo_curto: 4,
+        limiar_prioridade_baixa: 2,
+    });
+
+    // Tarefas curtas de fundo e tarefas longas interativas
+    for i in 1..=4 {
+        sistema.adicionar_processo(Processo::new(i, 3, 1));
+    }
+    for i in 5..=6 {
+        sistema.adicionar_processo(Processo::new(i, 20, 8));
+    }
+
+    sistema
+}
+
+/// Exemplo de paginação: quatro processos com working sets de 3 páginas
+/// disputando 8 quadros, o que leva o sistema ao thrashing
+pub fn exemplo_paginacao() -> Sistema {
+    let mut sistema = Sistema::new(2, 3, AlgoritmoEscalonamento::RoundRobin);
+    sistema.habilitar_paginacao(8, PoliticaSubstituicao::Lru, 4);
+
+    for i in 1..=4 {
+        sistema.adicionar_processo(Processo::new(i, 12, 1).com_referencias(&[0, 1, 2, 0, 1, 2]));
+    }
+
+    sistema
+}
+
+/// Produtor/consumidor com buffer limitado: semáforos `vazios` e `cheios`
+/// contam as posições do buffer e um mutex protege o acesso a ele
+pub fn exemplo_produtor_consumidor() -> Sistema {
+    const ITENS: u32 = 4;
+    let mut sistema = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+    let vazios = sistema.criar_semaforo("vazios", 2);
+    let cheios = sistema.criar_semaforo("cheios", 0);
+    let buffer = sistema.criar_mutex("buffer");
+
+    // Cada item ocupa duas unidades: entra na seção crítica e depois sai dela
+    let produtor = (0..ITENS).fold(Processo::new(1, 2 * ITENS, 1), |p, k| {
+        p.espera_em(2 * k, vazios).trava_em(2 * k, buffer)
+            .destrava_em(2 * k + 1, buffer).sinaliza_em(2 * k + 1, cheios)
+    });
+    let consumidor = (0..ITENS).fold(Processo::new(2, 2 * ITENS, 1), |p, k| {
+        p.espera_em(2 * k, cheios).trava_em(2 * k, buffer)
+            .destrava_em(2 * k + 1, buffer).sinaliza_em(2 * k + 1, vazios)
+    });
+    sistema.adicionar_processo(consumidor);
+    sistema.adicionar_processo(produtor);
+
+    sistema
+}
+
+/// Leitores/escritores: até 3 leitores simultâneos, cada um com uma permissão
+/// do semáforo `acesso`; o escritor junta todas as permissões para ter
+/// exclusividade, segurando o mutex `escrita` enquanto as recolhe
+pub fn exemplo_leitores_escritores() -> Sistema {
+    const PERMISSOES: u32 = 3;
+    let mut sistema = Sistema::new(4, 10, AlgoritmoEscalonamento::RoundRobin);
+    let acesso = sistema.criar_semaforo("acesso", PERMISSOES);
+    let escrita = sistema.criar_mutex("escrita");
+
+    let escritor = |id| {
+        let processo = (0..PERMISSOES).fold(Processo::new(id, 3, 1).trava_em(0, escrita), |p, _| p.espera_em(0, acesso));
+        (0..PERMISSOES).fold(processo.destrava_em(0, escrita), |p, _| p.sinaliza_em(2, acesso))
+    };
+    let leitor = |id| Processo::new(id, 4, 1).espera_em(0, acesso).sinaliza_em(3, acesso);
+
+    sistema.adicionar_processo(leitor(1));
+    sistema.adicionar_processo(leitor(2));
+    sistema.adicionar_processo(escritor(3));
+    sistema.adicionar_processo(leitor(4));
+    sistema.adicionar_processo(escritor(5));
+
+    sistema
+}
+
+/// Jantar dos filósofos com um mutex por garfo. Se todos pegassem primeiro o
+/// garfo da esquerda, cada um seguraria um garfo esperando o outro; o último
+/// filósofo pega primeiro o da direita e quebra a espera circular.
+pub fn exemplo_filosofos() -> Sistema {
+    const FILOSOFOS: usize = 5;
+    let mut sistema = Sistema::new(FILOSOFOS as u32, 10, AlgoritmoEscalonamento::RoundRobin);
+    let garfos: Vec<usize> = (0..FILOSOFOS).map(|i| sistema.criar_mutex(&format!("garfo {}", i))).collect();
+
+    for i in 0..FILOSOFOS {
+        let (esquerdo, direito) = (garfos[i], garfos[(i + 1) % FILOSOFOS]);
+        let (primeiro, segundo) = if i == FILOSOFOS - 1 { (direito, esquerdo) } else { (esquerdo, direito) };
+        sistema.adicionar_processo(
+            Processo::new(i as u32 + 1, 4, 1)
+                .trava_em(0, primeiro)
+                .trava_em(1, segundo)
+                .destrava_em(3, segundo)
+                .destrava_em(3, primeiro),
+        );
+    }
+
+    sistema
+}
+
+/// Inversão de prioridade da Mars Pathfinder: a tarefa de meteorologia (baixa
+/// prioridade) segura o mutex do barramento quando a gerência do barramento
+/// (alta) precisa dele, e a de comunicações (média, longa) toma o núcleo. Sem
+/// protocolo a tarefa de alta prioridade perde o deadline; com herança ou teto
+/// de prioridade a meteorologia termina a seção crítica antes.
+pub fn exemplo_mars_pathfinder(protocolo: ProtocoloPrioridade) -> Sistema {
+    let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::Prioridade);
+    sistema.definir_escalonador(Box::new(EscalonadorPrioridade::preemptivo(0)));
+    sistema.protocolo_prioridade = protocolo;
+    let barramento = sistema.criar_mutex("barramento");
+
+    let meteorologia = Processo::new(1, 6, 1).trava_em(0, barramento).destrava_em(5, barramento);
+    let gerencia = Processo::new(2, 3, 9).trava_em(0, barramento).destrava_em(2, barramento)
+        .com_deadline(8).com_chegada(2);
+    let comunicacoes = Processo::new(3, 10, 5).com_chegada(3);
+
+    sistema.adicionar_processo(meteorologia);
+    sistema.adicionar_processo(gerencia);
+    sistema.adicionar_processo(comunicacoes);
+
+    sistema
+}
+
+/// Mistura de processos limitados por CPU e por E/S: os limitados por E/S
+/// usam a CPU em rajadas curtas entre acessos ao disco
+pub fn exemplo_cpu_es() -> Sistema {
+    let mut sistema = Sistema::new(1, 4, AlgoritmoEscalonamento::RoundRobin);
+
+    for i in 1..=2 {
+        sistema.adicionar_processo(Processo::new(i, 20, 1));
+    }
+    for i in 3..=4 {
+        let rajadas: Vec<Rajada> = (0..4)
+            .flat_map(|_| [Rajada::Cpu(1), Rajada::Es(Recurso::Disco, 5)])
+            .collect();
+        sistema.adicionar_processo(Processo::new(i, 0, 1).com_rajadas(&rajadas));
+    }
+
+    sistema
+}
+
+/// Exemplo com vários processos disputando o disco em cilindros espalhados
+pub fn exemplo_disco(algoritmo: AlgoritmoDisco) -> Sistema {
+    let mut sistema = Sistema::new(2, 4, AlgoritmoEscalonamento::RoundRobin);
+    sistema.habilitar_disco(algoritmo);
+
+    let cilindros = [[98, 14], [183, 124], [37, 65], [122, 67]];
+    for (i, cilindros) in cilindros.iter().enumerate() {
+        let rajadas = [Rajada::Cpu(1), Rajada::Es(Recurso::Disco, 2), Rajada::Cpu(1),
+                       Rajada::Es(Recurso::Disco, 2), Rajada::Cpu(1)];
+        sistema.adicionar_processo(Processo::new(i as u32 + 1, 0, 1)
+            .com_rajadas(&rajadas)
+            .com_cilindros(cilindros));
+    }
+
+    sistema
+}
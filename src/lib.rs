@@ -0,0 +1,12 @@
+//! Simulador de escalonamento de processos com múltiplos núcleos e prevenção de deadlocks
+
+extern crate rand;
+
+pub mod sistema;
+pub mod escalonador;
+pub mod energia;
+pub mod deadlock;
+pub mod memoria;
+pub mod sincronizacao;
+pub mod disco;
+pub mod examples;
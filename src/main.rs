@@ -1,10 +1,16 @@
 //! Ponto de entrada principal do simulador de escalonamento de processos
 
-mod sistema;
-mod examples;
 extern crate rand;
-use crate::sistema::{Sistema, Processo, Recurso, AlgoritmoEscalonamento};
-use crate::examples::*;
+extern crate escalonador_processos;
+use escalonador_processos::sistema::{
+    Sistema, Processo, Recurso, Rajada, AlgoritmoEscalonamento, PoliticaPosicionamento,
+    ClasseNucleo, PosicionamentoEnergia, TratamentoDeadlock, CAPACIDADE_REFERENCIA, MEMORIA_TOTAL_MB,
+};
+use escalonador_processos::energia::Governador;
+use escalonador_processos::deadlock::{RecuperacaoDeadlock, CriterioVitima, ViolacaoOrdem};
+use escalonador_processos::memoria::PoliticaSubstituicao;
+use escalonador_processos::disco::AlgoritmoDisco;
+use escalonador_processos::escalonador::{Escalonador, EscalonadorPrioridade, EscalonadorMlfq, EscalonadorCfs, EscalonadorLoteria};
 use std::io::{self, Write, Read};
 use std::thread;
 use std::time::Duration;
@@ -26,28 +32,220 @@ fn configurar_sistema() -> Sistema {
     
     // Configurar número de núcleos
     let num_nucleos = ler_entrada_usize("Número de núcleos (1-16): ", 1, 16, 4) as u32;
+
+    // Topologia big.LITTLE: os últimos núcleos são de eficiência, com metade da velocidade
+    let nucleos_eficiencia = ler_entrada_usize(
+        &format!("Quantos são núcleos de eficiência (LITTLE, 50% da velocidade)? (0-{}): ", num_nucleos),
+        0, num_nucleos as usize, 0) as u32;
+    let topologia: Vec<(ClasseNucleo, u32)> = (0..num_nucleos)
+        .map(|i| if i < num_nucleos - nucleos_eficiencia {
+            (ClasseNucleo::Desempenho, CAPACIDADE_REFERENCIA)
+        } else {
+            (ClasseNucleo::Eficiencia, CAPACIDADE_REFERENCIA / 2)
+        })
+        .collect();
+    let posicionamento_energia = if nucleos_eficiencia > 0 &&
+        ler_entrada_usize("Posicionamento consciente de energia? (0 = não, 1 = sim): ", 0, 1, 1) == 1 {
+        Some(PosicionamentoEnergia { limiar_tempo_curto: 5, limiar_prioridade_baixa: 3 })
+    } else {
+        None
+    };
     
     // Configurar algoritmo
     let algoritmo = escolher_algoritmo();
     
     // Configurar quantum (se aplicável)
-    let quantum = if matches!(algoritmo, AlgoritmoEscalonamento::RoundRobin) {
+    let quantum = if matches!(algoritmo, AlgoritmoEscalonamento::RoundRobin | AlgoritmoEscalonamento::Mlfq |
+                                         AlgoritmoEscalonamento::Loteria | AlgoritmoEscalonamento::Stride) {
         ler_entrada_usize("Quantum (1-10): ", 1, 10, 3) as u32
     } else {
         1
     };
+
+    // Parâmetros específicos de cada política
+    let escalonador: Option<Box<dyn Escalonador>> = match algoritmo {
+        AlgoritmoEscalonamento::Prioridade => {
+            let intervalo = ler_entrada_usize("Envelhecimento: +1 de prioridade a cada N passos na fila (0 = desligado, 0-100): ", 0, 100, 10) as u32;
+            if ler_entrada_usize("Preemptivo? (0 = não, 1 = sim): ", 0, 1, 0) == 1 {
+                Some(Box::new(EscalonadorPrioridade::preemptivo(intervalo)))
+            } else {
+                Some(Box::new(EscalonadorPrioridade::new(intervalo)))
+            }
+        },
+        AlgoritmoEscalonamento::Mlfq => {
+            let niveis = ler_entrada_usize("Número de níveis da MLFQ (1-8): ", 1, 8, 3) as u32;
+            let boost = ler_entrada_usize("Intervalo de boost de prioridade (10-500): ", 10, 500, 50) as u32;
+            Some(Box::new(EscalonadorMlfq::com_niveis(niveis, quantum, boost)))
+        },
+        AlgoritmoEscalonamento::Cfs => {
+            let latencia = ler_entrada_usize("Latência alvo do CFS (4-100): ", 4, 100, 20) as u32;
+            let granularidade = ler_entrada_usize("Granularidade mínima do CFS (1-10): ", 1, 10, 2) as u32;
+            Some(Box::new(EscalonadorCfs::new(latencia, granularidade)))
+        },
+        AlgoritmoEscalonamento::Loteria => {
+            let semente = ler_entrada_usize("Semente do sorteio (0-999999): ", 0, 999_999, 42) as u64;
+            Some(Box::new(EscalonadorLoteria::new(quantum, semente)))
+        },
+        _ => None,
+    };
+    
+    // Configurar filas de prontos (global ou por núcleo com roubo de trabalho)
+    println!("\nFilas de prontos:");
+    println!("1. Fila global compartilhada");
+    println!("2. Fila por núcleo, posicionamento Round Robin");
+    println!("3. Fila por núcleo, posicionamento no menos carregado");
+    let posicionamento = match ler_entrada_usize("Opção (1-3): ", 1, 3, 1) {
+        2 => Some(PoliticaPosicionamento::RoundRobin),
+        3 => Some(PoliticaPosicionamento::MenosCarregado),
+        _ => None,
+    };
+    
+    // Custo de migração entre núcleos (aquecimento de cache)
+    let custo_migracao = ler_entrada_usize("Custo de migração entre núcleos (0-10 passos): ", 0, 10, 0) as u32;
+    
+    // Modelo de energia: estados de potência e governador de frequência (DVFS)
+    println!("\nModelo de energia:");
+    println!("0. Desligado");
+    println!("1. Governador performance (frequência máxima)");
+    println!("2. Governador powersave (frequência mínima)");
+    println!("3. Governador ondemand (conforme a carga)");
+    let governador = match ler_entrada_usize("Opção (0-3): ", 0, 3, 0) {
+        1 => Some(Governador::Desempenho),
+        2 => Some(Governador::Economia),
+        3 => Some(Governador::SobDemanda { limiar_subida: 80, limiar_descida: 30 }),
+        _ => None,
+    };
+    
+    // Tratamento de deadlocks
+    println!("\nTratamento de deadlock:");
+    println!("1. Detectar e encerrar um processo");
+    println!("2. Evitar (algoritmo do banqueiro)");
+    println!("3. Prevenir (ordem global de recursos, rejeitando pedidos fora de ordem)");
+    println!("4. Prevenir (ordem global de recursos, reordenando pedidos fora de ordem)");
+    let tratamento_deadlock = match ler_entrada_usize("Opção (1-4): ", 1, 4, 1) {
+        2 => TratamentoDeadlock::Evitacao,
+        3 => TratamentoDeadlock::Prevencao(ViolacaoOrdem::Rejeitar),
+        4 => TratamentoDeadlock::Prevencao(ViolacaoOrdem::Reordenar),
+        _ => TratamentoDeadlock::Deteccao,
+    };
+    let recuperacao = if tratamento_deadlock == TratamentoDeadlock::Deteccao {
+        println!("\nRecuperação de deadlock:");
+        println!("1. Encerrar todos os processos envolvidos");
+        println!("2. Encerrar uma vítima por vez");
+        println!("3. Preemptar os recursos da vítima e retroceder");
+        let estrategia = ler_entrada_usize("Opção (1-3): ", 1, 3, 2);
+        let criterio = if estrategia > 1 {
+            println!("Critério de escolha da vítima:");
+            println!("1. Menor prioridade");
+            println!("2. Menor progresso");
+            println!("3. Menos recursos alocados");
+            match ler_entrada_usize("Opção (1-3): ", 1, 3, 1) {
+                2 => CriterioVitima::MenorProgresso,
+                3 => CriterioVitima::MenosRecursos,
+                _ => CriterioVitima::MenorPrioridade,
+            }
+        } else {
+            CriterioVitima::MenorPrioridade
+        };
+        match estrategia {
+            1 => RecuperacaoDeadlock::EncerrarTodos,
+            3 => RecuperacaoDeadlock::PreemptarERetroceder(criterio),
+            _ => RecuperacaoDeadlock::EncerrarPorCusto(criterio),
+        }
+    } else {
+        RecuperacaoDeadlock::EncerrarPorCusto(CriterioVitima::MenorPrioridade)
+    };
+    let intervalo_checkpoint = match recuperacao {
+        RecuperacaoDeadlock::PreemptarERetroceder(_) =>
+            ler_entrada_usize("Checkpoint a cada N unidades executadas (0 = desligado, 0-20): ", 0, 20, 2) as u32,
+        _ => 0,
+    };
+    
+    // Memória paginada: quadros físicos e política de substituição
+    let quadros = ler_entrada_usize("\nQuadros de memória física para paginação (0 = desligada, 4-64): ", 0, 64, 0);
+    let paginacao = if quadros > 0 {
+        println!("Substituição de páginas:");
+        println!("1. FIFO");
+        println!("2. LRU");
+        println!("3. Clock (segunda chance)");
+        println!("4. Ótima");
+        let politica = match ler_entrada_usize("Opção (1-4): ", 1, 4, 2) {
+            1 => PoliticaSubstituicao::Fifo,
+            3 => PoliticaSubstituicao::Clock,
+            4 => PoliticaSubstituicao::Otima,
+            _ => PoliticaSubstituicao::Lru,
+        };
+        let latencia = ler_entrada_usize("Latência da falta de página (1-20 passos): ", 1, 20, 5) as u32;
+        Some((quadros.max(4), politica, latencia))
+    } else {
+        None
+    };
+    
+    // Disco com fila de pedidos: a E/S passa a depender da posição do cabeçote
+    println!("\nEscalonamento do disco:");
+    println!("0. Sem fila (E/S com duração fixa)");
+    println!("1. FCFS");
+    println!("2. SSTF");
+    println!("3. SCAN");
+    println!("4. C-SCAN");
+    println!("5. LOOK");
+    let algoritmo_disco = match ler_entrada_usize("Opção (0-5): ", 0, 5, 0) {
+        1 => Some(AlgoritmoDisco::Fcfs),
+        2 => Some(AlgoritmoDisco::Sstf),
+        3 => Some(AlgoritmoDisco::Scan),
+        4 => Some(AlgoritmoDisco::CScan),
+        5 => Some(AlgoritmoDisco::Look),
+        _ => None,
+    };
     
     // Configurar taxa de chegada de processos
     let taxa_chegada = ler_entrada_usize("Taxa de chegada de processos (1-100 por segundo): ", 1, 100, 20) as u32;
     
     println!("\nSistema configurado:");
-    println!("- Núcleos: {}", num_nucleos);
+    println!("- Núcleos: {} ({} de eficiência)", num_nucleos, nucleos_eficiencia);
     println!("- Algoritmo: {}", algoritmo);
     println!("- Quantum: {}", quantum);
+    match posicionamento {
+        Some(politica) => println!("- Filas: por núcleo ({})", politica),
+        None => println!("- Filas: global"),
+    }
+    if let Some(governador) = governador {
+        println!("- Energia: governador {}", governador);
+    }
+    println!("- Deadlock: {}", tratamento_deadlock);
+    if tratamento_deadlock == TratamentoDeadlock::Deteccao {
+        println!("- Recuperação: {}", recuperacao);
+    }
+    if let Some((quadros, politica, latencia)) = paginacao {
+        println!("- Paginação: {} quadros, {} (falta: {} passos)", quadros, politica, latencia);
+    }
+    if let Some(algoritmo_disco) = algoritmo_disco {
+        println!("- Disco: {}", algoritmo_disco);
+    }
     println!("- Taxa de chegada: {} processos/segundo", taxa_chegada);
     
-    let mut sistema = Sistema::new(num_nucleos, quantum, algoritmo);
+    let mut sistema = Sistema::com_topologia(&topologia, quantum, algoritmo);
+    sistema.posicionamento_energia = posicionamento_energia;
     sistema.taxa_chegada_processos = taxa_chegada;
+    sistema.custo_migracao = custo_migracao;
+    sistema.tratamento_deadlock = tratamento_deadlock;
+    sistema.recuperacao_deadlock = recuperacao;
+    sistema.intervalo_checkpoint = intervalo_checkpoint;
+    if let Some(escalonador) = escalonador {
+        sistema.definir_escalonador(escalonador);
+    }
+    if let Some(politica) = posicionamento {
+        sistema.habilitar_filas_por_nucleo(politica);
+    }
+    if let Some(governador) = governador {
+        sistema.habilitar_modelo_energia(governador);
+    }
+    if let Some((quadros, politica, latencia)) = paginacao {
+        sistema.habilitar_paginacao(quadros, politica, latencia);
+    }
+    if let Some(algoritmo_disco) = algoritmo_disco {
+        sistema.habilitar_disco(algoritmo_disco);
+    }
     
     sistema
 }
@@ -57,13 +255,29 @@ fn escolher_algoritmo() -> AlgoritmoEscalonamento {
     println!("1. Round Robin");
     println!("2. Por Prioridade");
     println!("3. Shortest Job First");
+    println!("4. Multi-Level Feedback Queue (MLFQ)");
+    println!("5. Completely Fair Scheduler (CFS)");
+    println!("6. Earliest Deadline First (EDF)");
+    println!("7. Rate Monotonic (RM)");
+    println!("8. Shortest Remaining Time First (SRTF)");
+    println!("9. Loteria");
+    println!("10. Stride");
+    println!("11. Highest Response Ratio Next (HRRN)");
     
     loop {
-        let escolha = ler_entrada_usize("Opção (1-3): ", 1, 3, 1);
+        let escolha = ler_entrada_usize("Opção (1-11): ", 1, 11, 1);
         match escolha {
             1 => return AlgoritmoEscalonamento::RoundRobin,
             2 => return AlgoritmoEscalonamento::Prioridade,
             3 => return AlgoritmoEscalonamento::ShortestJobFirst,
+            4 => return AlgoritmoEscalonamento::Mlfq,
+            5 => return AlgoritmoEscalonamento::Cfs,
+            6 => return AlgoritmoEscalonamento::Edf,
+            7 => return AlgoritmoEscalonamento::RateMonotonic,
+            8 => return AlgoritmoEscalonamento::Srtf,
+            9 => return AlgoritmoEscalonamento::Loteria,
+            10 => return AlgoritmoEscalonamento::Stride,
+            11 => return AlgoritmoEscalonamento::Hrrn,
             _ => println!("Opção inválida!"),
         }
     }
@@ -124,7 +338,7 @@ fn executar_gerenciador_tarefas(mut sistema: Sistema) {
         sistema.escalonar_interativo();
         
         // Mostrar status a cada 10 passos
-        if sistema.tempo_global % 10 == 0 {
+        if sistema.tempo_global.is_multiple_of(10) {
             mostrar_status_rapido(&sistema, contador_processos);
         }
         
@@ -164,7 +378,7 @@ fn gerar_processo_aleatorio(sistema: &mut Sistema, contador: &mut u32) {
     
     // Adicionar recursos aleatórios
     let recursos = [
-        (Recurso::Memoria(1024), rng.gen_range(1..4)),
+        (Recurso::Memoria, rng.gen_range(1..8) * 512),
         (Recurso::Disco, rng.gen_range(0..2)),
         (Recurso::Impressora, rng.gen_range(0..1)),
         (Recurso::Scanner, rng.gen_range(0..1)),
@@ -176,6 +390,13 @@ fn gerar_processo_aleatorio(sistema: &mut Sistema, contador: &mut u32) {
         }
     }
     
+    // Referências concentradas em poucas páginas (localidade)
+    if sistema.memoria_virtual.is_some() {
+        let paginas = rng.gen_range(2..6);
+        let referencias: Vec<u32> = (0..tempo_total).map(|_| rng.gen_range(0..paginas)).collect();
+        processo = processo.com_referencias(&referencias);
+    }
+    
     sistema.adicionar_processo(processo);
 }
 
@@ -189,18 +410,58 @@ fn adicionar_processo_manual(sistema: &mut Sistema, contador: &mut u32) {
     let mut processo = Processo::new(*contador, tempo, prioridade);
     
     // Adicionar recursos
-    let memoria = ler_entrada_usize("Memória necessária (0-4): ", 0, 4, 1) as u32;
+    let memoria = ler_entrada_usize("Memória necessária em MB (0-4096): ", 0, 4096, 1024) as u32;
     if memoria > 0 {
-        processo = processo.necessita_recurso(Recurso::Memoria(1024), memoria);
+        processo = processo.necessita_recurso(Recurso::Memoria, memoria);
+    }
+    if sistema.tratamento_deadlock == TratamentoDeadlock::Evitacao {
+        let maxima = ler_entrada_usize(&format!("Demanda máxima de memória em MB ({}-{}): ", memoria, MEMORIA_TOTAL_MB),
+                                       memoria as usize, MEMORIA_TOTAL_MB as usize, memoria as usize) as u32;
+        if maxima > memoria {
+            processo = processo.com_demanda_maxima(Recurso::Memoria, maxima);
+        }
+    }
+
+    // Rajadas de E/S no disco intercaladas com o tempo de CPU
+    let operacoes_es = ler_entrada_usize("Rajadas de E/S no disco (0 = só CPU, 0-5): ", 0, 5, 0) as u32;
+    if operacoes_es > 0 {
+        let duracao = ler_entrada_usize("Duração de cada E/S (1-20 passos): ", 1, 20, 5) as u32;
+        let mut rajadas = Vec::new();
+        for i in 0..=operacoes_es {
+            let cpu = tempo / (operacoes_es + 1) + u32::from(i < tempo % (operacoes_es + 1));
+            rajadas.push(Rajada::Cpu(cpu));
+            if i < operacoes_es {
+                rajadas.push(Rajada::Es(Recurso::Disco, duracao));
+            }
+        }
+        processo = processo.com_rajadas(&rajadas);
+    }
+
+    // Afinidade rígida com um núcleo
+    let maior_nucleo = sistema.nucleos.len();
+    let nucleo = ler_entrada_usize(&format!("Fixar em núcleo (0 = qualquer, 1-{} = núcleo 0-{}): ",
+                                            maior_nucleo, maior_nucleo - 1), 0, maior_nucleo, 0);
+    if nucleo > 0 {
+        processo = processo.fixado_em(nucleo as u32 - 1);
     }
+
+    // Tarefa periódica de tempo real (prazo igual ao período)
+    let periodo = ler_entrada_usize("Período (0 = aperiódico, 0-100): ", 0, 100, 0) as u32;
+    if periodo > 0 {
+        processo = processo.periodico(periodo);
+    }
+
+    // Chegada agendada: o processo só entra na fila de prontos no passo indicado
+    let atraso = ler_entrada_usize("Chegar daqui a quantos passos (0 = agora, 0-50): ", 0, 50, 0) as u32;
+    processo = processo.com_chegada(sistema.tempo_global + atraso);
     
     sistema.adicionar_processo(processo);
     println!("Processo {} adicionado com sucesso!", *contador);
 }
 
 fn mostrar_status_rapido(sistema: &Sistema, total_processos: u32) {
-    let processos_prontos = sistema.processos.len();
-    let processos_bloqueados = sistema.processos_bloqueados.len();
+    let processos_prontos = sistema.quantidade_prontos();
+    let processos_bloqueados = sistema.quantidade_bloqueados();
     let processos_executando = sistema.nucleos.iter()
         .filter(|n| n.processo_atual.is_some()).count();
     
@@ -0,0 +1,256 @@
+//! Memória virtual paginada: quadros físicos compartilhados, working sets e
+//! políticas de substituição de páginas
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+/// Política que escolhe a página a ser removida quando não há quadro livre
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoliticaSubstituicao {
+    /// Remove a página carregada há mais tempo
+    Fifo,
+    /// Remove a página usada há mais tempo
+    Lru,
+    /// Segunda chance: percorre os quadros em círculo limpando o bit de referência
+    Clock,
+    /// Remove a página cujo próximo uso está mais distante (exige conhecer o futuro)
+    Otima,
+}
+
+impl fmt::Display for PoliticaSubstituicao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoliticaSubstituicao::Fifo => write!(f, "FIFO"),
+            PoliticaSubstituicao::Lru => write!(f, "LRU"),
+            PoliticaSubstituicao::Clock => write!(f, "Clock"),
+            PoliticaSubstituicao::Otima => write!(f, "Ótima"),
+        }
+    }
+}
+
+/// Página de um processo carregada em um quadro físico
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quadro {
+    pub processo: u32,
+    pub pagina: u32,
+    /// Instante lógico (contagem de acessos) da carga e do último acesso
+    carregado_em: u64,
+    ultimo_acesso: u64,
+    referenciado: bool,
+}
+
+/// Gerenciador de memória paginada com substituição global: todos os
+/// processos disputam os mesmos quadros
+#[derive(Debug, Clone)]
+pub struct GerenciadorMemoria {
+    pub politica: PoliticaSubstituicao,
+    /// Passos que um processo fica bloqueado a cada falta de página
+    pub latencia_falta: u32,
+    /// Quantidade de referências recentes que formam o working set de um processo
+    pub janela_working_set: usize,
+    pub quadros: Vec<Option<Quadro>>,
+    ponteiro_relogio: usize,
+    relogio: u64,
+    historico: HashMap<u32, VecDeque<u32>>,
+    pub acessos: u32,
+    pub faltas: u32,
+    pub substituicoes: u32,
+    /// Passos em que a soma dos working sets excedeu os quadros
+    pub passos_thrashing: u32,
+}
+
+impl GerenciadorMemoria {
+    /// Cria o gerenciador com `num_quadros` quadros (no mínimo 1, para que
+    /// sempre haja onde carregar a página que faltou)
+    pub fn new(num_quadros: usize, politica: PoliticaSubstituicao, latencia_falta: u32) -> Self {
+        GerenciadorMemoria {
+            politica,
+            latencia_falta,
+            janela_working_set: 10,
+            quadros: vec![None; num_quadros.max(1)],
+            ponteiro_relogio: 0,
+            relogio: 0,
+            historico: HashMap::new(),
+            acessos: 0,
+            faltas: 0,
+            substituicoes: 0,
+            passos_thrashing: 0,
+        }
+    }
+
+    /// Acessa uma página. Retorna `true` se ela já estava carregada; em caso de
+    /// falta a página é carregada, substituindo outra se preciso. `proximo_uso`
+    /// informa a distância, em referências, até o próximo uso de uma página
+    /// (`None` = não será mais usada) e só é consultado pela política ótima.
+    pub fn acessar<F>(&mut self, processo: u32, pagina: u32, proximo_uso: F) -> bool
+    where
+        F: Fn(u32, u32) -> Option<usize>,
+    {
+        self.relogio += 1;
+        self.acessos += 1;
+        let historico = self.historico.entry(processo).or_default();
+        historico.push_back(pagina);
+        if historico.len() > self.janela_working_set {
+            historico.pop_front();
+        }
+
+        let relogio = self.relogio;
+        let residente = self.quadros.iter_mut().flatten()
+            .find(|q| q.processo == processo && q.pagina == pagina);
+        if let Some(quadro) = residente {
+            quadro.ultimo_acesso = relogio;
+            quadro.referenciado = true;
+            return true;
+        }
+
+        self.faltas += 1;
+        let indice = match self.quadros.iter().position(|q| q.is_none()) {
+            Some(livre) => livre,
+            None => {
+                self.substituicoes += 1;
+                self.escolher_vitima(proximo_uso)
+            }
+        };
+        self.quadros[indice] = Some(Quadro {
+            processo,
+            pagina,
+            carregado_em: relogio,
+            ultimo_acesso: relogio,
+            referenciado: true,
+        });
+        false
+    }
+
+    fn escolher_vitima<F>(&mut self, proximo_uso: F) -> usize
+    where
+        F: Fn(u32, u32) -> Option<usize>,
+    {
+        let ocupados = || self.quadros.iter().enumerate()
+            .filter_map(|(i, q)| q.as_ref().map(|q| (i, q)));
+        match self.politica {
+            PoliticaSubstituicao::Fifo =>
+                ocupados().min_by_key(|(_, q)| q.carregado_em).map_or(0, |(i, _)| i),
+            PoliticaSubstituicao::Lru =>
+                ocupados().min_by_key(|(_, q)| q.ultimo_acesso).map_or(0, |(i, _)| i),
+            PoliticaSubstituicao::Otima =>
+                ocupados()
+                    .max_by_key(|&(i, q)| (proximo_uso(q.processo, q.pagina).unwrap_or(usize::MAX),
+                                           std::cmp::Reverse(i)))
+                    .map_or(0, |(i, _)| i),
+            PoliticaSubstituicao::Clock => loop {
+                let indice = self.ponteiro_relogio;
+                self.ponteiro_relogio = (self.ponteiro_relogio + 1) % self.quadros.len();
+                match &mut self.quadros[indice] {
+                    Some(quadro) if quadro.referenciado => quadro.referenciado = false,
+                    _ => return indice,
+                }
+            },
+        }
+    }
+
+    /// Devolve os quadros de um processo que terminou
+    pub fn liberar_processo(&mut self, processo: u32) {
+        for quadro in self.quadros.iter_mut() {
+            if quadro.as_ref().is_some_and(|q| q.processo == processo) {
+                *quadro = None;
+            }
+        }
+        self.historico.remove(&processo);
+    }
+
+    /// Páginas distintas entre as últimas referências do processo
+    pub fn working_set(&self, processo: u32) -> usize {
+        self.historico.get(&processo).map_or(0, |h| {
+            let mut paginas: Vec<u32> = h.iter().copied().collect();
+            paginas.sort_unstable();
+            paginas.dedup();
+            paginas.len()
+        })
+    }
+
+    /// Soma dos working sets de todos os processos com páginas em uso
+    pub fn demanda_total(&self) -> usize {
+        self.historico.keys().map(|&p| self.working_set(p)).sum()
+    }
+
+    /// Os working sets não cabem nos quadros: os processos passam mais tempo
+    /// esperando páginas do que executando
+    pub fn em_thrashing(&self) -> bool {
+        self.demanda_total() > self.quadros.len()
+    }
+
+    pub fn quadros_ocupados(&self) -> usize {
+        self.quadros.iter().filter(|q| q.is_some()).count()
+    }
+
+    pub fn taxa_faltas(&self) -> f64 {
+        self.faltas as f64 / self.acessos.max(1) as f64
+    }
+
+    /// Chamado uma vez por passo da simulação
+    pub fn registrar_passo(&mut self) {
+        if self.em_thrashing() {
+            self.passos_thrashing += 1;
+        }
+    }
+}
+
+/// Distância até a próxima ocorrência de `pagina` em `referencias[inicio..fim]`
+pub fn proximo_uso(referencias: &[u32], inicio: usize, fim: usize, pagina: u32) -> Option<usize> {
+    if referencias.is_empty() {
+        return None;
+    }
+    (inicio..fim).position(|i| referencias[i % referencias.len()] == pagina)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sequência clássica de referências (Silberschatz) com 3 quadros
+    const REFERENCIAS: [u32; 20] = [7, 0, 1, 2, 0, 3, 0, 4, 2, 3, 0, 3, 2, 1, 2, 0, 1, 7, 0, 1];
+
+    fn contar_faltas(politica: PoliticaSubstituicao) -> u32 {
+        let mut memoria = GerenciadorMemoria::new(3, politica, 1);
+        for (i, &pagina) in REFERENCIAS.iter().enumerate() {
+            memoria.acessar(1, pagina, |_, p| proximo_uso(&REFERENCIAS, i + 1, REFERENCIAS.len(), p));
+        }
+        memoria.faltas
+    }
+
+    #[test]
+    fn test_faltas_por_politica() {
+        assert_eq!(contar_faltas(PoliticaSubstituicao::Fifo), 15);
+        assert_eq!(contar_faltas(PoliticaSubstituicao::Lru), 12);
+        assert_eq!(contar_faltas(PoliticaSubstituicao::Otima), 9);
+        assert_eq!(contar_faltas(PoliticaSubstituicao::Clock), 14);
+    }
+
+    #[test]
+    fn test_zero_quadros_vira_um() {
+        for politica in [PoliticaSubstituicao::Fifo, PoliticaSubstituicao::Lru,
+                         PoliticaSubstituicao::Clock, PoliticaSubstituicao::Otima] {
+            let mut memoria = GerenciadorMemoria::new(0, politica, 1);
+            assert_eq!(memoria.quadros.len(), 1);
+            assert!(!memoria.acessar(1, 0, |_, _| None));
+            assert!(!memoria.acessar(1, 1, |_, _| None));
+            assert_eq!(memoria.substituicoes, 1);
+        }
+    }
+
+    #[test]
+    fn test_working_set_e_thrashing() {
+        let mut memoria = GerenciadorMemoria::new(4, PoliticaSubstituicao::Lru, 1);
+        for pagina in [0, 1, 2, 0, 1, 2] {
+            memoria.acessar(1, pagina, |_, _| None);
+            memoria.acessar(2, pagina + 10, |_, _| None);
+        }
+        assert_eq!(memoria.working_set(1), 3);
+        assert_eq!(memoria.demanda_total(), 6);
+        assert!(memoria.em_thrashing());
+
+        memoria.liberar_processo(2);
+        assert!(!memoria.em_thrashing());
+        assert_eq!(memoria.quadros_ocupados(), 2);
+    }
+}
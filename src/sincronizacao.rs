@@ -0,0 +1,147 @@
+//! Primitivas de sincronização simuladas: semáforos contadores, mutexes e
+//! variáveis de condição, cada uma com sua própria fila de processos em espera
+
+use std::collections::VecDeque;
+use std::fmt;
+use crate::sistema::Processo;
+
+/// Operação de sincronização executada em um ponto do roteiro do processo.
+/// O `usize` é o identificador devolvido ao criar o objeto no `Sistema`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperacaoSincronizacao {
+    /// P (down): decrementa o semáforo ou espera até ele ser positivo
+    Esperar(usize),
+    /// V (up): acorda o primeiro processo em espera ou incrementa o semáforo
+    Sinalizar(usize),
+    Travar(usize),
+    Destravar(usize),
+    /// Libera o mutex da condição e espera até ser sinalizado
+    AguardarCondicao(usize),
+    /// Acorda um processo que aguarda a condição
+    SinalizarCondicao(usize),
+    /// Acorda todos os processos que aguardam a condição
+    DifundirCondicao(usize),
+}
+
+impl fmt::Display for OperacaoSincronizacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OperacaoSincronizacao::Esperar(_) => write!(f, "P"),
+            OperacaoSincronizacao::Sinalizar(_) => write!(f, "V"),
+            OperacaoSincronizacao::Travar(_) => write!(f, "lock"),
+            OperacaoSincronizacao::Destravar(_) => write!(f, "unlock"),
+            OperacaoSincronizacao::AguardarCondicao(_) => write!(f, "wait"),
+            OperacaoSincronizacao::SinalizarCondicao(_) => write!(f, "signal"),
+            OperacaoSincronizacao::DifundirCondicao(_) => write!(f, "broadcast"),
+        }
+    }
+}
+
+impl OperacaoSincronizacao {
+    /// Identificador do objeto sobre o qual a operação atua
+    pub fn objeto(&self) -> usize {
+        match *self {
+            OperacaoSincronizacao::Esperar(id)
+            | OperacaoSincronizacao::Sinalizar(id)
+            | OperacaoSincronizacao::Travar(id)
+            | OperacaoSincronizacao::Destravar(id)
+            | OperacaoSincronizacao::AguardarCondicao(id)
+            | OperacaoSincronizacao::SinalizarCondicao(id)
+            | OperacaoSincronizacao::DifundirCondicao(id) => id,
+        }
+    }
+
+    /// Indica se a operação pode ser aplicada a um objeto do tipo dado
+    pub fn aceita(&self, tipo: &TipoSincronizacao) -> bool {
+        matches!((self, tipo),
+            (OperacaoSincronizacao::Esperar(_), TipoSincronizacao::Semaforo { .. })
+            | (OperacaoSincronizacao::Sinalizar(_), TipoSincronizacao::Semaforo { .. })
+            | (OperacaoSincronizacao::Travar(_), TipoSincronizacao::Mutex { .. })
+            | (OperacaoSincronizacao::Destravar(_), TipoSincronizacao::Mutex { .. })
+            | (OperacaoSincronizacao::AguardarCondicao(_), TipoSincronizacao::Condicao { .. })
+            | (OperacaoSincronizacao::SinalizarCondicao(_), TipoSincronizacao::Condicao { .. })
+            | (OperacaoSincronizacao::DifundirCondicao(_), TipoSincronizacao::Condicao { .. }))
+    }
+}
+
+/// Operação feita quando o processo tiver executado `progresso` unidades
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassoSincronizacao {
+    pub progresso: u32,
+    pub operacao: OperacaoSincronizacao,
+}
+
+/// Estado próprio de cada tipo de objeto
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TipoSincronizacao {
+    Semaforo { valor: u32 },
+    Mutex { dono: Option<u32> },
+    /// Variável de condição associada a um mutex (semântica Mesa: quem é
+    /// sinalizado volta a disputar o mutex antes de continuar)
+    Condicao { mutex: usize },
+}
+
+impl fmt::Display for TipoSincronizacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TipoSincronizacao::Semaforo { valor } => write!(f, "semáforo (valor: {})", valor),
+            TipoSincronizacao::Mutex { dono: Some(dono) } => write!(f, "mutex (dono: P{})", dono),
+            TipoSincronizacao::Mutex { dono: None } => write!(f, "mutex (livre)"),
+            TipoSincronizacao::Condicao { mutex } => write!(f, "condição (mutex #{})", mutex),
+        }
+    }
+}
+
+/// Objeto de sincronização com a fila dos processos que esperam por ele
+#[derive(Debug, Clone)]
+pub struct ObjetoSincronizacao {
+    pub nome: String,
+    pub tipo: TipoSincronizacao,
+    /// Processos estacionados no objeto, na ordem em que serão acordados
+    pub fila: VecDeque<Processo>,
+    /// Vezes que um processo precisou esperar pelo objeto
+    pub bloqueios: u32,
+}
+
+impl ObjetoSincronizacao {
+    pub fn new(nome: &str, tipo: TipoSincronizacao) -> Self {
+        ObjetoSincronizacao {
+            nome: nome.to_string(),
+            tipo,
+            fila: VecDeque::new(),
+            bloqueios: 0,
+        }
+    }
+}
+
+impl fmt::Display for ObjetoSincronizacao {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.nome, self.tipo)?;
+        if !self.fila.is_empty() {
+            let ids: Vec<String> = self.fila.iter().map(|p| format!("P{}", p.id)).collect();
+            write!(f, ", em espera: {}", ids.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Protocolo que limita a inversão de prioridade em torno dos mutexes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocoloPrioridade {
+    /// O dono do mutex mantém a própria prioridade (inversão ilimitada)
+    Nenhum,
+    /// O dono herda a maior prioridade entre os processos que esperam pelo mutex
+    Heranca,
+    /// O dono assume o teto do mutex, a maior prioridade entre os processos que o usam
+    Teto,
+}
+
+impl fmt::Display for ProtocoloPrioridade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProtocoloPrioridade::Nenhum => write!(f, "Nenhum"),
+            ProtocoloPrioridade::Heranca => write!(f, "Herança de prioridade"),
+            ProtocoloPrioridade::Teto => write!(f, "Teto de prioridade"),
+        }
+    }
+}
@@ -1,23 +1,41 @@
 //! Módulo principal que implementa a lógica de escalonamento de processos
 
-use std::collections::{VecDeque, HashMap};
+use std::collections::{VecDeque, HashMap, BTreeSet};
 use std::fmt;
+use crate::escalonador::{
+    Escalonador, EscalonadorRoundRobin, EscalonadorPrioridade, EscalonadorSjf, EscalonadorMlfq,
+    EscalonadorCfs, EscalonadorTempoReal, EscalonadorSrtf, EscalonadorLoteria, EscalonadorStride,
+    EscalonadorHrrn,
+};
+use crate::energia::{EstadoEnergia, Governador, ModeloEnergia};
+use crate::deadlock::{
+    self, Deadlock, MotivoAborto, ProcessoAbortado, RecuperacaoDeadlock, CriterioVitima, ViolacaoOrdem,
+};
+use crate::memoria::{self, GerenciadorMemoria, PoliticaSubstituicao};
+use crate::disco::{AlgoritmoDisco, Disco, PedidoDisco, CILINDROS_DISCO};
+use crate::sincronizacao::{
+    ObjetoSincronizacao, OperacaoSincronizacao, PassoSincronizacao, ProtocoloPrioridade, TipoSincronizacao,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Recurso {
     Impressora,
     Scanner,
     Disco,
-    Memoria(u32), // Quantidade em MB
+    /// Memória fungível: as quantidades são em MB, retiradas de um único pool
+    Memoria,
 }
 
+/// Capacidade do pool de memória do sistema, em MB
+pub const MEMORIA_TOTAL_MB: u32 = 8192;
+
 impl fmt::Display for Recurso {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Recurso::Impressora => write!(f, "Impressora"),
             Recurso::Scanner => write!(f, "Scanner"),
             Recurso::Disco => write!(f, "Disco"),
-            Recurso::Memoria(mb) => write!(f, "Memória({}MB)", mb),
+            Recurso::Memoria => write!(f, "Memória"),
         }
     }
 }
@@ -28,6 +46,7 @@ pub enum EstadoProcesso {
     Executando,
     Bloqueado,
     Concluido,
+    Abortado,
 }
 
 impl fmt::Display for EstadoProcesso {
@@ -37,10 +56,56 @@ impl fmt::Display for EstadoProcesso {
             EstadoProcesso::Executando => write!(f, "Executando"),
             EstadoProcesso::Bloqueado => write!(f, "Bloqueado"),
             EstadoProcesso::Concluido => write!(f, "Concluído"),
+            EstadoProcesso::Abortado => write!(f, "Abortado"),
+        }
+    }
+}
+
+/// O que um passo do roteiro de recursos faz
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcaoRecurso {
+    Adquirir,
+    Liberar,
+}
+
+/// Passo do roteiro de recursos: ao atingir `progresso` unidades executadas,
+/// o processo adquire ou libera `quantidade` de `recurso`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassoRoteiro {
+    pub progresso: u32,
+    pub acao: AcaoRecurso,
+    pub recurso: Recurso,
+    pub quantidade: u32,
+}
+
+/// Rajada do ciclo de vida de um processo: uso de CPU ou espera por E/S
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rajada {
+    /// Unidades de trabalho executadas na CPU
+    Cpu(u32),
+    /// Passos de E/S no dispositivo, durante os quais o processo fica bloqueado
+    Es(Recurso, u32),
+}
+
+impl fmt::Display for Rajada {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rajada::Cpu(duracao) => write!(f, "CPU({})", duracao),
+            Rajada::Es(dispositivo, duracao) => write!(f, "E/S {}({})", dispositivo, duracao),
         }
     }
 }
 
+/// Estado salvo de um processo para permitir retrocesso sem recomeçar do zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub tempo: u32,
+    pub tempo_restante: u32,
+    pub passo_roteiro: usize,
+    /// Recursos do roteiro que o processo possuía no momento do checkpoint
+    pub recursos: HashMap<Recurso, u32>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Processo {
     pub id: u32,
@@ -48,9 +113,63 @@ pub struct Processo {
     pub tempo_total: u32,
     pub tempo_restante: u32,
     pub tempo_chegada: u32,
+    /// Passos executados desde o último despacho (consumo do quantum atual)
+    pub tempo_no_quantum: u32,
     pub estado: EstadoProcesso,
     pub recursos_alocados: HashMap<Recurso, u32>,
     pub recursos_necessarios: HashMap<Recurso, u32>,
+    /// Período de liberação de jobs (tarefas periódicas de tempo real)
+    pub periodo: Option<u32>,
+    /// Prazo relativo à liberação de cada job
+    pub deadline_relativa: Option<u32>,
+    /// Prazo absoluto do job atual
+    pub deadline: Option<u32>,
+    pub deadline_perdida: bool,
+    /// Passos aguardando na fila de prontos desde a última execução
+    pub tempo_espera: u32,
+    /// Núcleos em que o processo pode executar (`None` = qualquer um)
+    pub afinidade: Option<BTreeSet<u32>>,
+    /// Preferência não obrigatória de núcleo
+    pub nucleo_preferido: Option<u32>,
+    /// Núcleo onde o processo executou pela última vez
+    pub ultimo_nucleo: Option<u32>,
+    pub migracoes: u32,
+    /// Demanda máxima declarada por recurso (algoritmo do banqueiro); quando
+    /// ausente, vale a quantidade em `recursos_necessarios`
+    pub recursos_maximos: HashMap<Recurso, u32>,
+    /// Aquisições e liberações feitas durante a execução, em ordem de progresso.
+    /// Os recursos adquiridos pelo roteiro continuam com o processo quando ele
+    /// é preemptado ou bloqueado, ao contrário de `recursos_necessarios`.
+    pub roteiro: Vec<PassoRoteiro>,
+    /// Índice do próximo passo do roteiro
+    pub passo_roteiro: usize,
+    /// Aquisição do roteiro que deixou o processo bloqueado
+    pub pedido_pendente: Option<(Recurso, u32)>,
+    /// Vezes que o processo foi revertido para resolver um deadlock
+    pub reversoes: u32,
+    /// Checkpoints do job atual, do mais antigo ao mais recente
+    pub checkpoints: Vec<Checkpoint>,
+    /// Páginas referenciadas a cada unidade de trabalho (repetida em ciclo)
+    pub referencias_paginas: Vec<u32>,
+    pub faltas_pagina: u32,
+    /// Bloqueado por tempo (falta de página) até o passo indicado
+    pub bloqueado_ate: Option<u32>,
+    /// Operações em semáforos, mutexes e condições, em ordem de progresso
+    pub roteiro_sincronizacao: Vec<PassoSincronizacao>,
+    /// Índice da próxima operação de sincronização
+    pub passo_sincronizacao: usize,
+    /// Prioridade acima da base recebida por herança ou teto enquanto segura um mutex
+    pub prioridade_herdada: Option<i32>,
+    /// Rajadas alternadas de CPU e E/S; vazio = uma única rajada de CPU de `tempo_total`
+    pub rajadas: Vec<Rajada>,
+    /// Rajadas de E/S já feitas no job atual
+    pub es_realizadas: usize,
+    /// Dispositivo da E/S em andamento enquanto o processo está bloqueado
+    pub dispositivo_es: Option<Recurso>,
+    /// Passos na fila de prontos ao longo do job atual
+    pub espera_acumulada: u32,
+    /// Cilindro acessado por cada rajada de E/S em disco, em ordem (repetido em ciclo)
+    pub cilindros_es: Vec<u32>,
 }
 
 impl Processo {
@@ -61,9 +180,36 @@ impl Processo {
             tempo_total,
             tempo_restante: tempo_total,
             tempo_chegada: 0,
+            tempo_no_quantum: 0,
             estado: EstadoProcesso::Pronto,
             recursos_alocados: HashMap::new(),
             recursos_necessarios: HashMap::new(),
+            periodo: None,
+            deadline_relativa: None,
+            deadline: None,
+            deadline_perdida: false,
+            tempo_espera: 0,
+            afinidade: None,
+            nucleo_preferido: None,
+            ultimo_nucleo: None,
+            migracoes: 0,
+            recursos_maximos: HashMap::new(),
+            roteiro: Vec::new(),
+            passo_roteiro: 0,
+            pedido_pendente: None,
+            reversoes: 0,
+            checkpoints: Vec::new(),
+            referencias_paginas: Vec::new(),
+            faltas_pagina: 0,
+            bloqueado_ate: None,
+            roteiro_sincronizacao: Vec::new(),
+            passo_sincronizacao: 0,
+            prioridade_herdada: None,
+            rajadas: Vec::new(),
+            es_realizadas: 0,
+            dispositivo_es: None,
+            espera_acumulada: 0,
+            cilindros_es: Vec::new(),
         }
     }
 
@@ -71,21 +217,452 @@ impl Processo {
         self.recursos_necessarios.insert(recurso, quantidade);
         self
     }
+
+    /// Declara a demanda máxima do processo por um recurso
+    pub fn com_demanda_maxima(mut self, recurso: Recurso, quantidade: u32) -> Self {
+        self.recursos_maximos.insert(recurso, quantidade);
+        self
+    }
+
+    /// Adquire `quantidade` de `recurso` quando o processo tiver executado `progresso` unidades
+    pub fn adquire_em(self, progresso: u32, recurso: Recurso, quantidade: u32) -> Self {
+        self.com_passo(PassoRoteiro { progresso, acao: AcaoRecurso::Adquirir, recurso, quantidade })
+    }
+
+    /// Libera `quantidade` de `recurso` quando o processo tiver executado `progresso` unidades
+    pub fn libera_em(self, progresso: u32, recurso: Recurso, quantidade: u32) -> Self {
+        self.com_passo(PassoRoteiro { progresso, acao: AcaoRecurso::Liberar, recurso, quantidade })
+    }
+
+    /// Faz P no semáforo quando o processo tiver executado `progresso` unidades
+    pub fn espera_em(self, progresso: u32, semaforo: usize) -> Self {
+        self.com_sincronizacao(progresso, OperacaoSincronizacao::Esperar(semaforo))
+    }
+
+    /// Faz V no semáforo quando o processo tiver executado `progresso` unidades
+    pub fn sinaliza_em(self, progresso: u32, semaforo: usize) -> Self {
+        self.com_sincronizacao(progresso, OperacaoSincronizacao::Sinalizar(semaforo))
+    }
+
+    pub fn trava_em(self, progresso: u32, mutex: usize) -> Self {
+        self.com_sincronizacao(progresso, OperacaoSincronizacao::Travar(mutex))
+    }
+
+    pub fn destrava_em(self, progresso: u32, mutex: usize) -> Self {
+        self.com_sincronizacao(progresso, OperacaoSincronizacao::Destravar(mutex))
+    }
+
+    /// Aguarda a condição (o processo deve possuir o mutex dela)
+    pub fn aguarda_condicao_em(self, progresso: u32, condicao: usize) -> Self {
+        self.com_sincronizacao(progresso, OperacaoSincronizacao::AguardarCondicao(condicao))
+    }
+
+    pub fn sinaliza_condicao_em(self, progresso: u32, condicao: usize) -> Self {
+        self.com_sincronizacao(progresso, OperacaoSincronizacao::SinalizarCondicao(condicao))
+    }
+
+    pub fn difunde_condicao_em(self, progresso: u32, condicao: usize) -> Self {
+        self.com_sincronizacao(progresso, OperacaoSincronizacao::DifundirCondicao(condicao))
+    }
+
+    fn com_sincronizacao(mut self, progresso: u32, operacao: OperacaoSincronizacao) -> Self {
+        let posicao = self.roteiro_sincronizacao.iter().take_while(|p| p.progresso <= progresso).count();
+        self.roteiro_sincronizacao.insert(posicao, PassoSincronizacao { progresso, operacao });
+        self
+    }
+
+    /// Define a sequência de páginas acessadas; a unidade `i` do job referencia
+    /// a página `referencias[i % referencias.len()]`
+    pub fn com_referencias(mut self, referencias: &[u32]) -> Self {
+        self.referencias_paginas = referencias.to_vec();
+        self
+    }
+
+    /// Página referenciada pela próxima unidade de trabalho
+    pub fn pagina_atual(&self) -> Option<u32> {
+        if self.referencias_paginas.is_empty() {
+            return None;
+        }
+        let indice = self.progresso() as usize % self.referencias_paginas.len();
+        Some(self.referencias_paginas[indice])
+    }
+
+    fn com_passo(mut self, passo: PassoRoteiro) -> Self {
+        let posicao = self.roteiro.iter().take_while(|p| p.progresso <= passo.progresso).count();
+        self.roteiro.insert(posicao, passo);
+        self
+    }
+
+    /// Define a sequência de rajadas; `tempo_total` passa a ser a soma das rajadas de CPU
+    pub fn com_rajadas(mut self, rajadas: &[Rajada]) -> Self {
+        self.rajadas = rajadas.to_vec();
+        self.tempo_total = rajadas.iter()
+            .map(|r| match r { Rajada::Cpu(duracao) => *duracao, Rajada::Es(..) => 0 })
+            .sum();
+        self.tempo_restante = self.tempo_total;
+        self
+    }
+
+    /// Rajada de E/S que o progresso já alcançou e que ainda não foi feita
+    pub fn es_pendente(&self) -> Option<(Recurso, u32)> {
+        let mut cpu = 0;
+        let mut indice_es = 0;
+        for rajada in &self.rajadas {
+            match *rajada {
+                Rajada::Cpu(duracao) => cpu += duracao,
+                Rajada::Es(dispositivo, duracao) => {
+                    if indice_es == self.es_realizadas {
+                        return Some((dispositivo, duracao)).filter(|_| self.progresso() >= cpu);
+                    }
+                    indice_es += 1;
+                }
+            }
+        }
+        None
+    }
+
+    /// Define os cilindros acessados pelas rajadas de E/S em disco
+    pub fn com_cilindros(mut self, cilindros: &[u32]) -> Self {
+        self.cilindros_es = cilindros.to_vec();
+        self
+    }
+
+    /// Cilindro da próxima rajada de E/S; sem cilindros definidos, os pedidos
+    /// se espalham pelo disco de forma determinística
+    pub fn cilindro_es(&self, cilindros: u32) -> u32 {
+        let indice = self.es_realizadas;
+        let cilindro = match self.cilindros_es.len() {
+            0 => self.id as u64 * 37 + indice as u64 * 91,
+            n => self.cilindros_es[indice % n] as u64,
+        };
+        (cilindro % cilindros.max(1) as u64) as u32
+    }
+
+    /// Soma das rajadas de E/S do job
+    pub fn tempo_total_es(&self) -> u32 {
+        self.rajadas.iter()
+            .map(|r| match r { Rajada::Es(_, duracao) => *duracao, Rajada::Cpu(_) => 0 })
+            .sum()
+    }
+
+    /// Processo que passa mais tempo esperando E/S do que usando a CPU
+    pub fn limitado_por_es(&self) -> bool {
+        self.tempo_total_es() > self.tempo_total
+    }
+
+    /// Prioridade usada pelo escalonador: a base ou a herdada, se maior
+    pub fn prioridade_efetiva(&self) -> i32 {
+        self.prioridade_herdada.map_or(self.prioridade, |herdada| herdada.max(self.prioridade))
+    }
+
+    /// Unidades de trabalho já executadas no job atual
+    pub fn progresso(&self) -> u32 {
+        self.tempo_total.saturating_sub(self.tempo_restante)
+    }
+
+    /// O que o processo está esperando para voltar a executar: a aquisição
+    /// pendente do roteiro ou os recursos necessários ao despacho
+    pub fn pedido_em_espera(&self) -> HashMap<Recurso, u32> {
+        if self.bloqueado_ate.is_some() || self.dispositivo_es.is_some() {
+            return HashMap::new();
+        }
+        match self.pedido_pendente {
+            Some((recurso, quantidade)) => HashMap::from([(recurso, quantidade)]),
+            None => self.recursos_necessarios.clone(),
+        }
+    }
+
+    /// Demanda máxima efetiva: a declarada ou, no mínimo, o que o processo
+    /// necessita ao despacho somado a tudo que o roteiro adquire
+    pub fn demanda_maxima(&self) -> HashMap<Recurso, u32> {
+        let mut maxima = self.recursos_necessarios.clone();
+        for passo in self.roteiro.iter().filter(|p| p.acao == AcaoRecurso::Adquirir) {
+            *maxima.entry(passo.recurso).or_insert(0) += passo.quantidade;
+        }
+        for (recurso, &quantidade) in &self.recursos_maximos {
+            let atual = maxima.entry(*recurso).or_insert(0);
+            *atual = (*atual).max(quantidade);
+        }
+        maxima
+    }
+
+    /// Quanto o processo ainda pode pedir de cada recurso além do que já possui
+    pub fn necessidade_restante(&self) -> HashMap<Recurso, u32> {
+        self.demanda_maxima().into_iter()
+            .map(|(recurso, maxima)| {
+                let alocado = self.recursos_alocados.get(&recurso).copied().unwrap_or(0);
+                (recurso, maxima.saturating_sub(alocado))
+            })
+            .collect()
+    }
+
+    /// Define o prazo relativo à liberação do processo
+    pub fn com_deadline(mut self, deadline_relativa: u32) -> Self {
+        self.deadline_relativa = Some(deadline_relativa);
+        self
+    }
+
+    /// Torna o processo uma tarefa periódica; sem deadline explícito o prazo é o próprio período
+    pub fn periodico(mut self, periodo: u32) -> Self {
+        self.periodo = Some(periodo);
+        self.deadline_relativa.get_or_insert(periodo);
+        self
+    }
+
+    /// Restringe o processo aos núcleos informados (afinidade rígida)
+    pub fn com_afinidade(mut self, nucleos: &[u32]) -> Self {
+        self.afinidade = Some(nucleos.iter().copied().collect());
+        self
+    }
+
+    /// Fixa o processo em um único núcleo
+    pub fn fixado_em(self, nucleo: u32) -> Self {
+        self.com_afinidade(&[nucleo])
+    }
+
+    /// Indica um núcleo preferido; o processo ainda pode migrar se ele estiver ocupado
+    pub fn com_nucleo_preferido(mut self, nucleo: u32) -> Self {
+        self.nucleo_preferido = Some(nucleo);
+        self
+    }
+
+    /// Define o passo em que o processo chega; até lá ele aguarda fora da fila de prontos
+    pub fn com_chegada(mut self, tempo: u32) -> Self {
+        self.tempo_chegada = tempo;
+        self
+    }
+
+    pub fn pode_executar_em(&self, nucleo: u32) -> bool {
+        self.afinidade.as_ref().is_none_or(|nucleos| nucleos.contains(&nucleo))
+    }
+
+    /// Prepara o job liberado em `tempo`, recalculando o deadline absoluto
+    fn liberar_job(&mut self, tempo: u32) {
+        self.tempo_chegada = tempo;
+        self.tempo_restante = self.tempo_total;
+        self.deadline = self.deadline_relativa.map(|d| tempo + d);
+        self.deadline_perdida = false;
+        self.estado = EstadoProcesso::Pronto;
+        self.passo_roteiro = 0;
+        self.pedido_pendente = None;
+        self.bloqueado_ate = None;
+        self.passo_sincronizacao = 0;
+        self.es_realizadas = 0;
+        self.dispositivo_es = None;
+        self.espera_acumulada = 0;
+        self.checkpoints.clear();
+    }
+
+    /// Salva o progresso e os recursos do roteiro; chamado enquanto o processo
+    /// executa, por isso os recursos do despacho são descontados
+    fn registrar_checkpoint(&mut self, tempo: u32) {
+        let mut recursos = self.recursos_alocados.clone();
+        for (recurso, &quantidade) in &self.recursos_necessarios {
+            if let Some(alocado) = recursos.get_mut(recurso) {
+                *alocado = alocado.saturating_sub(quantidade);
+            }
+        }
+        recursos.retain(|_, q| *q > 0);
+        self.checkpoints.push(Checkpoint {
+            tempo,
+            tempo_restante: self.tempo_restante,
+            passo_roteiro: self.passo_roteiro,
+            recursos,
+        });
+    }
+}
+
+/// Classe do núcleo em arquiteturas heterogêneas (big.LITTLE)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClasseNucleo {
+    Desempenho,
+    Eficiencia,
+}
+
+impl fmt::Display for ClasseNucleo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClasseNucleo::Desempenho => write!(f, "Desempenho"),
+            ClasseNucleo::Eficiencia => write!(f, "Eficiência"),
+        }
+    }
 }
 
+/// Capacidade de referência: um núcleo com capacidade 100 executa uma
+/// unidade de `tempo_restante` por passo
+pub const CAPACIDADE_REFERENCIA: u32 = 100;
+
 #[derive(Debug)]
 pub struct Nucleo {
     pub id: u32,
     pub processo_atual: Option<Processo>,
     pub tempo_ocioso: u32,
+    pub classe: ClasseNucleo,
+    /// Velocidade relativa em porcentagem de `CAPACIDADE_REFERENCIA`
+    pub capacidade: u32,
+    /// Fração acumulada de unidades de trabalho ainda não executadas
+    credito: u32,
+    /// Unidades de `tempo_restante` executadas neste núcleo
+    pub trabalho_realizado: u32,
+    pub tempo_ativo: u32,
+    /// Fila de prontos própria, usada no modo de filas por núcleo
+    pub fila_local: VecDeque<Processo>,
+    /// Tamanho da fila local amostrado a cada `INTERVALO_AMOSTRAGEM` passos
+    pub historico_fila: Vec<u32>,
+    pub roubos: u32,
+    /// `None` desliga a contabilidade de energia e mantém a frequência nominal
+    pub modelo_energia: Option<ModeloEnergia>,
+    pub estado_energia: EstadoEnergia,
+    /// Índice do nível de frequência atual em `ModeloEnergia::niveis`
+    pub nivel_frequencia: usize,
+    pub energia_consumida: f64,
+    pub trocas_frequencia: u32,
+    pub despertares: u32,
+    /// Passos em que o núcleo fica parado antes de executar o processo
+    /// despachado: latência de despertar mais o aquecimento após uma migração
+    atraso_restante: u32,
+    passos_ociosos: u32,
+    /// Passos ativos na janela atual do governador
+    ocupacao_janela: u32,
 }
 
 impl Nucleo {
-    fn new(id: u32) -> Self {
+    pub fn new(id: u32) -> Self {
+        Self::com_classe(id, ClasseNucleo::Desempenho, CAPACIDADE_REFERENCIA)
+    }
+
+    pub fn com_classe(id: u32, classe: ClasseNucleo, capacidade: u32) -> Self {
         Nucleo {
             id,
             processo_atual: None,
             tempo_ocioso: 0,
+            classe,
+            capacidade,
+            credito: 0,
+            trabalho_realizado: 0,
+            tempo_ativo: 0,
+            fila_local: VecDeque::new(),
+            historico_fila: Vec::new(),
+            roubos: 0,
+            modelo_energia: None,
+            estado_energia: EstadoEnergia::Ocioso,
+            nivel_frequencia: 0,
+            energia_consumida: 0.0,
+            trocas_frequencia: 0,
+            despertares: 0,
+            atraso_restante: 0,
+            passos_ociosos: 0,
+            ocupacao_janela: 0,
+        }
+    }
+
+    pub fn definir_modelo_energia(&mut self, modelo: ModeloEnergia) {
+        self.nivel_frequencia = modelo.nivel_inicial();
+        self.modelo_energia = Some(modelo);
+    }
+
+    /// Frequência atual em porcentagem da nominal
+    pub fn frequencia(&self) -> u32 {
+        self.modelo_energia.as_ref().map_or(100, |m| m.frequencia(self.nivel_frequencia))
+    }
+
+    /// Processos na fila local mais o que está executando
+    pub fn carga(&self) -> usize {
+        self.fila_local.len() + self.processo_atual.is_some() as usize
+    }
+
+    /// Unidades de trabalho que o núcleo executa neste passo, conforme a capacidade
+    fn unidades_no_passo(&mut self) -> u32 {
+        self.credito += self.capacidade * self.frequencia() / 100;
+        let unidades = self.credito / CAPACIDADE_REFERENCIA;
+        self.credito %= CAPACIDADE_REFERENCIA;
+        unidades
+    }
+
+    /// Chamado ao despachar um processo: um núcleo dormindo paga a latência de despertar
+    fn despertar(&mut self) -> u32 {
+        let latencia = match &self.modelo_energia {
+            Some(modelo) if self.estado_energia == EstadoEnergia::Dormindo => modelo.latencia_despertar,
+            _ => 0,
+        };
+        if self.estado_energia == EstadoEnergia::Dormindo {
+            self.despertares += 1;
+        }
+        self.estado_energia = EstadoEnergia::Ativo;
+        self.passos_ociosos = 0;
+        self.atraso_restante = latencia;
+        latencia
+    }
+
+    /// Atualiza o estado de potência, acumula a energia do passo e aplica o governador
+    fn contabilizar_energia(&mut self, tempo: u32) {
+        let modelo = match &self.modelo_energia {
+            Some(modelo) => modelo,
+            None => return,
+        };
+
+        if self.processo_atual.is_some() {
+            self.estado_energia = EstadoEnergia::Ativo;
+            self.passos_ociosos = 0;
+            self.ocupacao_janela += 1;
+        } else {
+            self.passos_ociosos += 1;
+            self.estado_energia = if self.passos_ociosos > modelo.ociosidade_para_dormir {
+                EstadoEnergia::Dormindo
+            } else {
+                EstadoEnergia::Ocioso
+            };
+        }
+        self.energia_consumida += modelo.potencia(self.estado_energia, self.nivel_frequencia);
+
+        if (tempo + 1).is_multiple_of(INTERVALO_AMOSTRAGEM) {
+            let ocupacao = self.ocupacao_janela * 100 / INTERVALO_AMOSTRAGEM;
+            let nivel = modelo.proximo_nivel(self.nivel_frequencia, ocupacao);
+            if nivel != self.nivel_frequencia {
+                self.nivel_frequencia = nivel;
+                self.trocas_frequencia += 1;
+            }
+            self.ocupacao_janela = 0;
+        }
+    }
+}
+
+/// Limiares do posicionamento consciente de energia: processos curtos ou de
+/// baixa prioridade vão para núcleos de eficiência, os demais para os de desempenho
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PosicionamentoEnergia {
+    pub limiar_tempo_curto: u32,
+    pub limiar_prioridade_baixa: i32,
+}
+
+impl PosicionamentoEnergia {
+    pub fn classe_para(&self, processo: &Processo) -> ClasseNucleo {
+        if processo.tempo_restante <= self.limiar_tempo_curto
+            || processo.prioridade <= self.limiar_prioridade_baixa {
+            ClasseNucleo::Eficiencia
+        } else {
+            ClasseNucleo::Desempenho
+        }
+    }
+}
+
+/// Intervalo, em passos, entre amostras do tamanho das filas por núcleo
+pub const INTERVALO_AMOSTRAGEM: u32 = 10;
+
+/// Como novos processos são distribuídos entre as filas locais dos núcleos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoliticaPosicionamento {
+    /// Um núcleo de cada vez, em rodízio
+    RoundRobin,
+    /// Núcleo com menor carga (fila local + processo em execução)
+    MenosCarregado,
+}
+
+impl fmt::Display for PoliticaPosicionamento {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoliticaPosicionamento::RoundRobin => write!(f, "Round Robin"),
+            PoliticaPosicionamento::MenosCarregado => write!(f, "Menos carregado"),
         }
     }
 }
@@ -95,6 +672,14 @@ pub enum AlgoritmoEscalonamento {
     RoundRobin,
     Prioridade,
     ShortestJobFirst,
+    Mlfq,
+    Cfs,
+    Edf,
+    RateMonotonic,
+    Srtf,
+    Loteria,
+    Stride,
+    Hrrn,
 }
 
 impl fmt::Display for AlgoritmoEscalonamento {
@@ -103,10 +688,175 @@ impl fmt::Display for AlgoritmoEscalonamento {
             AlgoritmoEscalonamento::RoundRobin => write!(f, "Round Robin"),
             AlgoritmoEscalonamento::Prioridade => write!(f, "Por Prioridade"),
             AlgoritmoEscalonamento::ShortestJobFirst => write!(f, "Shortest Job First"),
+            AlgoritmoEscalonamento::Mlfq => write!(f, "Multi-Level Feedback Queue"),
+            AlgoritmoEscalonamento::Cfs => write!(f, "Completely Fair Scheduler"),
+            AlgoritmoEscalonamento::Edf => write!(f, "Earliest Deadline First"),
+            AlgoritmoEscalonamento::RateMonotonic => write!(f, "Rate Monotonic"),
+            AlgoritmoEscalonamento::Srtf => write!(f, "Shortest Remaining Time First"),
+            AlgoritmoEscalonamento::Loteria => write!(f, "Loteria"),
+            AlgoritmoEscalonamento::Stride => write!(f, "Stride"),
+            AlgoritmoEscalonamento::Hrrn => write!(f, "Highest Response Ratio Next"),
+        }
+    }
+}
+
+impl AlgoritmoEscalonamento {
+    /// Cria a política de escalonamento correspondente ao algoritmo
+    pub fn criar_escalonador(self, quantum: u32) -> Box<dyn Escalonador> {
+        match self {
+            AlgoritmoEscalonamento::RoundRobin => Box::new(EscalonadorRoundRobin::new(quantum)),
+            AlgoritmoEscalonamento::Prioridade => Box::new(EscalonadorPrioridade::new(0)),
+            AlgoritmoEscalonamento::ShortestJobFirst => Box::new(EscalonadorSjf),
+            // Padrão: 3 níveis (q, 2q, 4q) e boost a cada 50 passos
+            AlgoritmoEscalonamento::Mlfq => Box::new(EscalonadorMlfq::com_niveis(3, quantum, 50)),
+            // O CFS não usa quantum fixo: latência alvo de 20 e granularidade de 2 passos
+            AlgoritmoEscalonamento::Cfs => Box::new(EscalonadorCfs::new(20, 2)),
+            AlgoritmoEscalonamento::Edf => Box::new(EscalonadorTempoReal::edf()),
+            AlgoritmoEscalonamento::RateMonotonic => Box::new(EscalonadorTempoReal::rate_monotonic()),
+            AlgoritmoEscalonamento::Srtf => Box::new(EscalonadorSrtf),
+            AlgoritmoEscalonamento::Loteria => Box::new(EscalonadorLoteria::new(quantum, 42)),
+            AlgoritmoEscalonamento::Stride => Box::new(EscalonadorStride::new(quantum)),
+            AlgoritmoEscalonamento::Hrrn => Box::new(EscalonadorHrrn),
         }
     }
 }
 
+/// Como o sistema lida com deadlocks de recursos
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TratamentoDeadlock {
+    /// Detecta periodicamente e encerra um processo para quebrar o impasse
+    Deteccao,
+    /// Só concede recursos que mantêm o sistema em estado seguro (algoritmo do banqueiro)
+    Evitacao,
+    /// Impede a espera circular: recursos só podem ser pedidos em ordem crescente de posto
+    Prevencao(ViolacaoOrdem),
+}
+
+impl fmt::Display for TratamentoDeadlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TratamentoDeadlock::Deteccao => write!(f, "Detecção e encerramento"),
+            TratamentoDeadlock::Evitacao => write!(f, "Evitação (algoritmo do banqueiro)"),
+            TratamentoDeadlock::Prevencao(violacao) =>
+                write!(f, "Prevenção por ordem global de recursos (violações: {})", violacao),
+        }
+    }
+}
+
+/// Eventos relevantes registrados durante a simulação
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evento {
+    DeadlinePerdida { tempo: u32, processo: u32, deadline: u32 },
+    /// Concessão adiada porque levaria o sistema a um estado inseguro
+    PedidoAdiado { tempo: u32, processo: u32 },
+    /// Processo recusado na admissão: demanda máxima acima da capacidade do sistema
+    ProcessoRejeitado { tempo: u32, processo: u32 },
+    DeadlockDetectado { tempo: u32, deadlock: Deadlock },
+    /// Roteiro que pede `pedido` enquanto possui `possuido`, de posto maior ou igual
+    ViolacaoOrdemRecursos { tempo: u32, processo: u32, pedido: Recurso, possuido: Recurso, rejeitado: bool },
+    ProcessoAbortado { tempo: u32, processo: u32, motivo: MotivoAborto },
+    /// Recursos tomados e processo reiniciado, perdendo o progresso indicado
+    ProcessoRevertido { tempo: u32, processo: u32, progresso_perdido: u32 },
+    /// Página ausente dos quadros: o processo bloqueia até ela ser carregada
+    FaltaPagina { tempo: u32, processo: u32, pagina: u32, desbloqueio: u32 },
+    /// Processo recusado na admissão: o roteiro usa um objeto inexistente ou de outro tipo
+    SincronizacaoInvalida { tempo: u32, processo: u32, operacao: OperacaoSincronizacao },
+    /// Condição criada sobre um objeto que não é um mutex; quem a usar será recusado
+    CondicaoSemMutex { tempo: u32, condicao: usize, mutex: usize },
+}
+
+impl fmt::Display for Evento {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Evento::DeadlinePerdida { tempo, processo, deadline } =>
+                write!(f, "[T={}] Processo {} perdeu o deadline (prazo T={})", tempo, processo, deadline),
+            Evento::PedidoAdiado { tempo, processo } =>
+                write!(f, "[T={}] Pedido de recursos do processo {} adiado (estado inseguro)", tempo, processo),
+            Evento::ProcessoRejeitado { tempo, processo } =>
+                write!(f, "[T={}] Processo {} rejeitado: demanda máxima excede os recursos do sistema", tempo, processo),
+            Evento::DeadlockDetectado { tempo, deadlock } =>
+                write!(f, "[T={}] Deadlock detectado, {}", tempo, deadlock),
+            Evento::ViolacaoOrdemRecursos { tempo, processo, pedido, possuido, rejeitado } =>
+                write!(f, "[T={}] Processo {} pede {} enquanto possui {}, fora da ordem global: {}",
+                       tempo, processo, pedido, possuido,
+                       if *rejeitado { "processo rejeitado" } else { "roteiro reordenado" }),
+            Evento::ProcessoAbortado { tempo, processo, motivo } =>
+                write!(f, "[T={}] Processo {} abortado: {}", tempo, processo, motivo),
+            Evento::ProcessoRevertido { tempo, processo, progresso_perdido } =>
+                write!(f, "[T={}] Processo {} teve os recursos preemptados e foi revertido ({} unidades perdidas)",
+                       tempo, processo, progresso_perdido),
+            Evento::FaltaPagina { tempo, processo, pagina, desbloqueio } =>
+                write!(f, "[T={}] Falta de página: processo {} aguarda a página {} até T={}",
+                       tempo, processo, pagina, desbloqueio),
+            Evento::SincronizacaoInvalida { tempo, processo, operacao } =>
+                write!(f, "[T={}] Processo {} rejeitado: {} no objeto #{}, que não existe ou é de outro tipo",
+                       tempo, processo, operacao, operacao.objeto()),
+            Evento::CondicaoSemMutex { tempo, condicao, mutex } =>
+                write!(f, "[T={}] Condição #{} associada ao objeto #{}, que não é um mutex",
+                       tempo, condicao, mutex),
+        }
+    }
+}
+
+/// Tempos dos processos concluídos de um perfil (limitados por CPU ou por E/S)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResumoPerfil {
+    pub concluidos: u32,
+    /// Soma dos tempos de retorno (conclusão - chegada)
+    pub retorno_total: u32,
+    /// Soma dos passos passados na fila de prontos
+    pub espera_total: u32,
+}
+
+impl ResumoPerfil {
+    fn registrar(&mut self, retorno: u32, espera: u32) {
+        self.concluidos += 1;
+        self.retorno_total += retorno;
+        self.espera_total += espera;
+    }
+
+    pub fn retorno_medio(&self) -> f64 {
+        self.retorno_total as f64 / self.concluidos.max(1) as f64
+    }
+
+    pub fn espera_media(&self) -> f64 {
+        self.espera_total as f64 / self.concluidos.max(1) as f64
+    }
+}
+
+/// Contadores acumulados ao longo da simulação
+#[derive(Debug, Clone, Default)]
+pub struct Estatisticas {
+    pub processos_concluidos: u32,
+    pub jobs_liberados: u32,
+    pub deadlines_perdidas: u32,
+    /// Maior tempo contínuo que um processo passou na fila de prontos
+    pub espera_maxima: u32,
+    /// Processos roubados por núcleos ociosos (modo de filas por núcleo)
+    pub roubos: u32,
+    /// Despachos em um núcleo diferente do último em que o processo executou
+    pub migracoes: u32,
+    /// Concessões adiadas pelo algoritmo do banqueiro
+    pub pedidos_adiados: u32,
+    pub deadlocks_detectados: u32,
+    pub reversoes: u32,
+    pub checkpoints: u32,
+    /// Unidades executadas e depois perdidas por abortos e retrocessos
+    pub trabalho_desperdicado: u32,
+    /// Processos com pedidos fora da ordem global (modo de prevenção)
+    pub violacoes_ordem: u32,
+    /// Vezes que um processo teve a prioridade elevada por herança ou teto
+    pub elevacoes_prioridade: u32,
+    /// Passos em que um processo esperava um mutex cujo dono não executava
+    /// enquanto outro de prioridade menor executava
+    pub passos_inversao: u32,
+    /// Rajadas de E/S iniciadas e passos totais de E/S
+    pub operacoes_es: u32,
+    pub passos_es: u32,
+    pub limitados_cpu: ResumoPerfil,
+    pub limitados_es: ResumoPerfil,
+}
+
 /// Sistema principal que gerencia todos os componentes
 pub struct Sistema {
     pub nucleos: Vec<Nucleo>,
@@ -114,23 +864,55 @@ pub struct Sistema {
     pub processos_bloqueados: Vec<Processo>,
     pub recursos_disponiveis: HashMap<Recurso, u32>,
     pub algoritmo: AlgoritmoEscalonamento,
+    pub escalonador: Box<dyn Escalonador>,
     pub tempo_global: u32,
     pub quantum: u32,
     pub taxa_chegada_processos: u32,
+    /// Próximos jobs de tarefas periódicas, com o tempo de liberação
+    pub liberacoes_pendentes: Vec<(u32, Processo)>,
+    /// Processos submetidos com chegada futura, admitidos quando `tempo_global` a alcança
+    pub chegadas_pendentes: Vec<Processo>,
+    pub eventos: Vec<Evento>,
+    pub estatisticas: Estatisticas,
+    /// `Some` ativa as filas por núcleo com roubo de trabalho; `None` usa a fila global
+    pub posicionamento: Option<PoliticaPosicionamento>,
+    proximo_nucleo: usize,
+    /// Passos extras cobrados de um processo ao migrar de núcleo (aquecimento de cache)
+    pub custo_migracao: u32,
+    pub posicionamento_energia: Option<PosicionamentoEnergia>,
+    pub tratamento_deadlock: TratamentoDeadlock,
+    pub recuperacao_deadlock: RecuperacaoDeadlock,
+    pub processos_abortados: Vec<ProcessoAbortado>,
+    /// Unidades de progresso entre checkpoints dos processos em execução (0 = desligado)
+    pub intervalo_checkpoint: u32,
+    /// Tipos de recurso em ordem crescente de posto, usada no modo de prevenção
+    pub ordem_recursos: Vec<Recurso>,
+    /// Memória paginada; `None` desliga a paginação
+    pub memoria_virtual: Option<GerenciadorMemoria>,
+    /// Semáforos, mutexes e condições, indexados pelo identificador devolvido na criação
+    pub objetos_sincronizacao: Vec<ObjetoSincronizacao>,
+    pub protocolo_prioridade: ProtocoloPrioridade,
+    /// Dispositivos com fila de pedidos; E/S em um recurso sem dispositivo tem duração fixa
+    pub dispositivos: HashMap<Recurso, Disco>,
 }
 
 impl Sistema {
     pub fn new(num_nucleos: u32, quantum: u32, algoritmo: AlgoritmoEscalonamento) -> Self {
-        let mut nucleos = Vec::with_capacity(num_nucleos as usize);
-        for i in 0..num_nucleos {
-            nucleos.push(Nucleo::new(i));
-        }
+        let topologia = vec![(ClasseNucleo::Desempenho, CAPACIDADE_REFERENCIA); num_nucleos as usize];
+        Self::com_topologia(&topologia, quantum, algoritmo)
+    }
+
+    /// Cria o sistema com núcleos heterogêneos: um par (classe, capacidade) por núcleo
+    pub fn com_topologia(topologia: &[(ClasseNucleo, u32)], quantum: u32, algoritmo: AlgoritmoEscalonamento) -> Self {
+        let nucleos = topologia.iter().enumerate()
+            .map(|(i, &(classe, capacidade))| Nucleo::com_classe(i as u32, classe, capacidade))
+            .collect();
 
         let recursos = HashMap::from([
             (Recurso::Impressora, 2),
             (Recurso::Scanner, 1),
             (Recurso::Disco, 3),
-            (Recurso::Memoria(1024), 8), // 8GB total
+            (Recurso::Memoria, MEMORIA_TOTAL_MB),
         ]);
 
         Sistema {
@@ -139,109 +921,1012 @@ impl Sistema {
             processos_bloqueados: Vec::new(),
             recursos_disponiveis: recursos,
             algoritmo,
+            escalonador: algoritmo.criar_escalonador(quantum),
             tempo_global: 0,
             quantum,
             taxa_chegada_processos: 20, // padrão
+            liberacoes_pendentes: Vec::new(),
+            chegadas_pendentes: Vec::new(),
+            eventos: Vec::new(),
+            estatisticas: Estatisticas::default(),
+            posicionamento: None,
+            proximo_nucleo: 0,
+            custo_migracao: 0,
+            posicionamento_energia: None,
+            tratamento_deadlock: TratamentoDeadlock::Deteccao,
+            recuperacao_deadlock: RecuperacaoDeadlock::EncerrarPorCusto(CriterioVitima::MenorPrioridade),
+            processos_abortados: Vec::new(),
+            intervalo_checkpoint: 0,
+            ordem_recursos: vec![Recurso::Memoria, Recurso::Disco, Recurso::Impressora, Recurso::Scanner],
+            memoria_virtual: None,
+            objetos_sincronizacao: Vec::new(),
+            protocolo_prioridade: ProtocoloPrioridade::Nenhum,
+            dispositivos: HashMap::new(),
         }
     }
 
-    pub fn adicionar_processo(&mut self, processo: Processo) {
-        let mut processo = processo;
-        processo.estado = EstadoProcesso::Pronto;
-        self.processos.push_back(processo);
+    /// Liga o modelo de energia em todos os núcleos com os valores padrão da classe
+    pub fn habilitar_modelo_energia(&mut self, governador: Governador) {
+        for nucleo in &mut self.nucleos {
+            nucleo.definir_modelo_energia(ModeloEnergia::padrao(nucleo.classe, governador));
+        }
     }
 
-    /// Verifica se existe algum deadlock no sistema usando o algoritmo do banqueiro
-    pub fn verificar_deadlock(&self) -> bool {
-        let mut trabalho = self.recursos_disponiveis.clone();
-        let processos = self.processos.iter().chain(self.processos_bloqueados.iter());
+    /// Liga a paginação com `quadros` quadros físicos compartilhados por todos os
+    /// processos (0 é tratado como 1 quadro)
+    pub fn habilitar_paginacao(&mut self, quadros: usize, politica: PoliticaSubstituicao, latencia_falta: u32) {
+        self.memoria_virtual = Some(GerenciadorMemoria::new(quadros, politica, latencia_falta));
+    }
+
+    /// Transforma `Recurso::Disco` em um dispositivo com fila de pedidos e busca
+    pub fn habilitar_disco(&mut self, algoritmo: AlgoritmoDisco) {
+        self.dispositivos.insert(Recurso::Disco, Disco::new(algoritmo, CILINDROS_DISCO, 0));
+    }
+
+    /// Cria um semáforo contador e devolve seu identificador
+    pub fn criar_semaforo(&mut self, nome: &str, valor: u32) -> usize {
+        self.criar_objeto(nome, TipoSincronizacao::Semaforo { valor })
+    }
+
+    pub fn criar_mutex(&mut self, nome: &str) -> usize {
+        self.criar_objeto(nome, TipoSincronizacao::Mutex { dono: None })
+    }
 
-        let mut finish = HashMap::new();
-        for p in processos.clone() {
-            finish.insert(p.id, false);
+    /// Cria uma variável de condição protegida pelo mutex informado
+    pub fn criar_condicao(&mut self, nome: &str, mutex: usize) -> usize {
+        let condicao = self.criar_objeto(nome, TipoSincronizacao::Condicao { mutex });
+        if self.mutex_da_condicao(condicao).is_none() {
+            let evento = Evento::CondicaoSemMutex { tempo: self.tempo_global, condicao, mutex };
+            self.registrar_evento(evento);
         }
+        condicao
+    }
 
-        loop {
-            let mut encontrou = false;
+    fn criar_objeto(&mut self, nome: &str, tipo: TipoSincronizacao) -> usize {
+        self.objetos_sincronizacao.push(ObjetoSincronizacao::new(nome, tipo));
+        self.objetos_sincronizacao.len() - 1
+    }
 
-            for p in processos.clone() {
-                if !finish[&p.id] {
-                    let mut recursos_suficientes = true;
+    /// A operação atua sobre um objeto existente do tipo certo (e, nas
+    /// condições, associado a um mutex)
+    fn operacao_valida(&self, operacao: OperacaoSincronizacao) -> bool {
+        match self.objetos_sincronizacao.get(operacao.objeto()) {
+            Some(objeto) if operacao.aceita(&objeto.tipo) => match objeto.tipo {
+                TipoSincronizacao::Condicao { .. } => self.mutex_da_condicao(operacao.objeto()).is_some(),
+                _ => true,
+            },
+            _ => false,
+        }
+    }
 
-                    for (recurso, &necessario) in &p.recursos_necessarios {
-                        let disponivel = trabalho.get(recurso).unwrap_or(&0);
-                        if necessario > *disponivel {
-                            recursos_suficientes = false;
-                            break;
-                        }
-                    }
+    /// Teto de prioridade do mutex: a maior prioridade base entre os processos,
+    /// presentes ou ainda por chegar, que o travam
+    pub fn teto_prioridade(&self, mutex: usize) -> Option<i32> {
+        let trava = OperacaoSincronizacao::Travar(mutex);
+        self.todos_processos()
+            .chain(self.liberacoes_pendentes.iter().map(|(_, p)| p))
+            .chain(self.chegadas_pendentes.iter())
+            .filter(|p| p.roteiro_sincronizacao.iter().any(|passo| passo.operacao == trava))
+            .map(|p| p.prioridade)
+            .max()
+    }
 
-                    if recursos_suficientes {
-                        encontrou = true;
-                        finish.insert(p.id, true);
+    /// Processos bloqueados por recursos, por tempo ou em filas de sincronização
+    pub fn quantidade_bloqueados(&self) -> usize {
+        self.processos_bloqueados.len() +
+            self.objetos_sincronizacao.iter().map(|o| o.fila.len()).sum::<usize>()
+    }
 
-                        for (recurso, &alocado) in &p.recursos_alocados {
-                            *trabalho.entry(*recurso).or_insert(0) += alocado;
-                        }
-                    }
-                }
-            }
+    /// Energia consumida por todos os núcleos até agora (W × passo)
+    pub fn energia_total(&self) -> f64 {
+        self.nucleos.iter().map(|n| n.energia_consumida).sum()
+    }
 
-            if !encontrou {
-                break;
-            }
-        }
+    /// Produto energia-atraso: energia total × tempo decorrido da simulação
+    pub fn produto_energia_atraso(&self) -> f64 {
+        self.energia_total() * self.tempo_global as f64
+    }
 
-        finish.values().any(|&f| !f)
+    /// Substitui a política de escalonamento (ex.: uma política definida fora do crate)
+    pub fn definir_escalonador(&mut self, escalonador: Box<dyn Escalonador>) {
+        self.escalonador = escalonador;
     }
 
-    fn alocar_recursos(&mut self, processo: &Processo) -> bool {
-        for (recurso, &necessario) in &processo.recursos_necessarios {
-            let disponivel = self.recursos_disponiveis.get(recurso).unwrap_or(&0);
-            if necessario > *disponivel {
-                return false;
-            }
+    pub fn adicionar_processo(&mut self, processo: Processo) {
+        if processo.tempo_chegada > self.tempo_global {
+            println!("[T={}] Processo {} agendado para chegar em T={}",
+                     self.tempo_global, processo.id, processo.tempo_chegada);
+            self.chegadas_pendentes.push(processo);
+            return;
         }
+        self.admitir_processo(processo);
+    }
 
-        for (recurso, &necessario) in &processo.recursos_necessarios {
-            *self.recursos_disponiveis.get_mut(recurso).unwrap() -= necessario;
+    /// Admite um processo que já chegou na fila de prontos
+    fn admitir_processo(&mut self, processo: Processo) {
+        let mut processo = processo;
+        // Submetido depois do início: a chegada é o instante da submissão
+        processo.tempo_chegada = processo.tempo_chegada.max(self.tempo_global);
+        if self.tratamento_deadlock == TratamentoDeadlock::Evitacao {
+            let totais = self.recursos_totais();
+            let excede = processo.demanda_maxima().iter()
+                .any(|(r, &q)| q > totais.get(r).copied().unwrap_or(0));
+            if excede {
+                let evento = Evento::ProcessoRejeitado { tempo: self.tempo_global, processo: processo.id };
+                self.registrar_evento(evento);
+                return;
+            }
+        }
+        let invalida = processo.roteiro_sincronizacao.iter()
+            .map(|passo| passo.operacao)
+            .find(|&operacao| !self.operacao_valida(operacao));
+        if let Some(operacao) = invalida {
+            let evento = Evento::SincronizacaoInvalida { tempo: self.tempo_global, processo: processo.id, operacao };
+            self.registrar_evento(evento);
+            return;
+        }
+        processo.estado = EstadoProcesso::Pronto;
+        if processo.deadline.is_none() {
+            processo.deadline = processo.deadline_relativa.map(|d| self.tempo_global + d);
+        }
+        if let TratamentoDeadlock::Prevencao(violacao) = self.tratamento_deadlock {
+            if !self.aplicar_ordem_recursos(&mut processo, violacao) {
+                return;
+            }
         }
+        self.escalonador.ao_chegar(&processo, self.tempo_global);
+        self.fila_destino(&processo, None).push_back(processo);
+    }
 
-        true
+    /// Ativa as filas de prontos por núcleo com a política de posicionamento dada
+    pub fn habilitar_filas_por_nucleo(&mut self, politica: PoliticaPosicionamento) {
+        self.posicionamento = Some(politica);
+        // Processos já na fila global são redistribuídos
+        while let Some(processo) = self.processos.pop_front() {
+            self.fila_destino(&processo, None).push_back(processo);
+        }
     }
 
-    fn liberar_recursos(&mut self, processo: &Processo) {
-        for (recurso, &alocado) in &processo.recursos_alocados {
-            *self.recursos_disponiveis.entry(*recurso).or_insert(0) += alocado;
+    /// Fila onde um processo pronto deve entrar: a global ou a local de um
+    /// núcleo. Processos preemptados voltam para a fila do núcleo onde rodavam;
+    /// os demais vão para o núcleo preferido ou seguem a política de
+    /// posicionamento, sempre respeitando a afinidade.
+    fn fila_destino(&mut self, processo: &Processo, nucleo_anterior: Option<usize>) -> &mut VecDeque<Processo> {
+        let politica = match self.posicionamento {
+            Some(politica) if !self.nucleos.is_empty() => politica,
+            _ => return &mut self.processos,
+        };
+
+        let mut permitidos: Vec<usize> = (0..self.nucleos.len())
+            .filter(|&i| processo.pode_executar_em(self.nucleos[i].id))
+            .collect();
+        if let Some(energia) = self.posicionamento_energia {
+            let classe = energia.classe_para(processo);
+            let da_classe: Vec<usize> = permitidos.iter().copied()
+                .filter(|&i| self.nucleos[i].classe == classe)
+                .collect();
+            if !da_classe.is_empty() {
+                permitidos = da_classe;
+            }
         }
+        let preferido = processo.nucleo_preferido
+            .and_then(|id| permitidos.iter().copied().find(|&i| self.nucleos[i].id == id));
+
+        let indice = match nucleo_anterior.or(preferido) {
+            Some(indice) => indice,
+            // Afinidade com núcleos inexistentes: fica na fila do primeiro núcleo
+            None if permitidos.is_empty() => 0,
+            None => match politica {
+                PoliticaPosicionamento::RoundRobin => {
+                    let indice = permitidos[self.proximo_nucleo % permitidos.len()];
+                    self.proximo_nucleo += 1;
+                    indice
+                },
+                PoliticaPosicionamento::MenosCarregado => {
+                    permitidos.iter().copied().min_by_key(|&i| self.nucleos[i].carga()).unwrap_or(0)
+                },
+            },
+        };
+        &mut self.nucleos[indice].fila_local
     }
 
-    pub fn escalonar(&mut self) {
-        // Verificação de deadlock (sem problemas de borrowing)
-        if self.tempo_global % 10 == 0 && self.verificar_deadlock() {
-            println!("[!] Deadlock detectado no tempo {}! Tomando ações corretivas...", self.tempo_global);
-            if let Some(processo) = self.processos.pop_front() {
-                println!("[!] Processo {} terminado para resolver deadlock", processo.id);
-                self.liberar_recursos(&processo);
+    /// Se o processo pode ser despachado no núcleo: respeita a afinidade e cede
+    /// a vez ao núcleo preferido (ou da classe indicada pelo posicionamento
+    /// consciente de energia) enquanto ele estiver ocioso
+    fn elegivel_para(&self, processo: &Processo, nucleo: usize) -> bool {
+        let id = self.nucleos[nucleo].id;
+        if !processo.pode_executar_em(id) {
+            return false;
+        }
+        if let Some(energia) = self.posicionamento_energia {
+            let classe = energia.classe_para(processo);
+            let outra_classe_ociosa = self.nucleos.iter().any(|n| {
+                n.classe == classe && n.processo_atual.is_none() && processo.pode_executar_em(n.id)
+            });
+            if self.nucleos[nucleo].classe != classe && outra_classe_ociosa {
+                return false;
             }
         }
+        match processo.nucleo_preferido {
+            Some(preferido) if preferido != id => !self.nucleos.iter().any(|n| {
+                n.id == preferido && n.processo_atual.is_none() && processo.pode_executar_em(preferido)
+            }),
+            _ => true,
+        }
+    }
 
-        // Fase 1: Processar núcleos
-        let mut processos_concluidos = Vec::new();
+    /// Todos os processos prontos, da fila global e das filas locais
+    pub fn processos_prontos<'a>(&'a self) -> impl Iterator<Item = &'a Processo> + Clone + 'a {
+        self.processos.iter().chain(self.nucleos.iter().flat_map(|n| n.fila_local.iter()))
+    }
+
+    pub fn quantidade_prontos(&self) -> usize {
+        self.processos.len() + self.nucleos.iter().map(|n| n.fila_local.len()).sum::<usize>()
+    }
+
+    /// Núcleo ocioso sem trabalho local rouba um processo da fila mais cheia
+    fn roubar_trabalho(&mut self, ladrao: usize) -> bool {
+        let id_ladrao = self.nucleos[ladrao].id;
+        let vitima = (0..self.nucleos.len())
+            .filter(|&i| i != ladrao)
+            .filter(|&i| self.nucleos[i].fila_local.iter().any(|p| p.pode_executar_em(id_ladrao)))
+            .max_by_key(|&i| self.nucleos[i].fila_local.len());
+
+        let vitima = match vitima {
+            Some(vitima) => vitima,
+            None => return false,
+        };
+        // Rouba do fim da fila: o processo que esperaria mais na vítima
+        let posicao = self.nucleos[vitima].fila_local.iter()
+            .rposition(|p| p.pode_executar_em(id_ladrao));
+        if let Some(processo) = posicao.and_then(|i| self.nucleos[vitima].fila_local.remove(i)) {
+            println!("[T={}] Núcleo {} roubou o processo {} do núcleo {}",
+                     self.tempo_global, self.nucleos[ladrao].id, processo.id, self.nucleos[vitima].id);
+            self.nucleos[ladrao].fila_local.push_back(processo);
+            self.nucleos[ladrao].roubos += 1;
+            self.estatisticas.roubos += 1;
+            return true;
+        }
+        false
+    }
+
+    /// Retira da fila apropriada o próximo processo a executar no núcleo dado
+    fn retirar_proximo(&mut self, nucleo: usize) -> Option<Processo> {
+        let fila = if self.posicionamento.is_none() { &self.processos } else { &self.nucleos[nucleo].fila_local };
+        let elegiveis: Vec<bool> = fila.iter().map(|p| self.elegivel_para(p, nucleo)).collect();
+
+        if self.posicionamento.is_some() && !elegiveis.contains(&true) {
+            if !self.roubar_trabalho(nucleo) {
+                return None;
+            }
+            return self.retirar_proximo(nucleo);
+        }
+
+        let fila = if self.posicionamento.is_none() { &mut self.processos } else { &mut self.nucleos[nucleo].fila_local };
+        escolher_elegivel(self.escalonador.as_mut(), fila, &elegiveis, self.tempo_global)
+    }
+
+    pub fn registrar_evento(&mut self, evento: Evento) {
+        println!("{}", evento);
+        self.eventos.push(evento);
+    }
+
+    /// Admite os processos cuja chegada agendada foi alcançada, na ordem de submissão
+    fn admitir_chegadas(&mut self) {
+        let tempo = self.tempo_global;
+        let (chegaram, pendentes) = std::mem::take(&mut self.chegadas_pendentes)
+            .into_iter()
+            .partition(|p| p.tempo_chegada <= tempo);
+        self.chegadas_pendentes = pendentes;
+        let mut chegaram: Vec<Processo> = chegaram;
+        chegaram.sort_by_key(|p| p.tempo_chegada);
+        for processo in chegaram {
+            println!("[T={}] Processo {} chegou", tempo, processo.id);
+            self.admitir_processo(processo);
+        }
+    }
+
+    /// Coloca na fila de prontos os jobs periódicos cujo tempo de liberação chegou
+    fn liberar_jobs_periodicos(&mut self) {
+        let mut i = 0;
+        while i < self.liberacoes_pendentes.len() {
+            if self.liberacoes_pendentes[i].0 <= self.tempo_global {
+                let (tempo, mut processo) = self.liberacoes_pendentes.remove(i);
+                processo.liberar_job(tempo);
+                let deadline = processo.deadline.map(|d| format!(" (deadline T={})", d)).unwrap_or_default();
+                println!("[T={}] Job do processo {} liberado{}", self.tempo_global, processo.id, deadline);
+                self.estatisticas.jobs_liberados += 1;
+                self.escalonador.ao_chegar(&processo, self.tempo_global);
+                self.fila_destino(&processo, None).push_back(processo);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Agenda o próximo job de uma tarefa periódica concluída
+    fn agendar_proximo_job(&mut self, processo: Processo) {
+        if let (Some(periodo), Some(deadline), Some(relativa)) =
+            (processo.periodo, processo.deadline, processo.deadline_relativa) {
+            let liberacao_atual = deadline - relativa;
+            let proxima = (liberacao_atual + periodo).max(self.tempo_global);
+            self.liberacoes_pendentes.push((proxima, processo));
+        }
+    }
+
+    fn todos_processos_mut(&mut self) -> impl Iterator<Item = &mut Processo> {
+        self.nucleos.iter_mut()
+            .flat_map(|n| n.processo_atual.iter_mut().chain(n.fila_local.iter_mut()))
+            .chain(self.processos.iter_mut())
+            .chain(self.processos_bloqueados.iter_mut())
+            .chain(self.objetos_sincronizacao.iter_mut().flat_map(|o| o.fila.iter_mut()))
+    }
+
+    /// Registra a perda de deadline de jobs que ainda não terminaram no prazo
+    fn verificar_deadlines(&mut self) {
+        let tempo = self.tempo_global;
+        let mut perdidos = Vec::new();
+        for processo in self.todos_processos_mut() {
+            if let Some(deadline) = processo.deadline {
+                if !processo.deadline_perdida && processo.tempo_restante > 0 && deadline <= tempo {
+                    processo.deadline_perdida = true;
+                    perdidos.push(Evento::DeadlinePerdida { tempo, processo: processo.id, deadline });
+                }
+            }
+        }
+
+        for evento in perdidos {
+            self.estatisticas.deadlines_perdidas += 1;
+            self.registrar_evento(evento);
+        }
+    }
+
+    /// Posto global do recurso; recursos fora de `ordem_recursos` ficam por último
+    pub fn posto_recurso(&self, recurso: &Recurso) -> usize {
+        self.ordem_recursos.iter()
+            .position(|r| r == recurso)
+            .unwrap_or(self.ordem_recursos.len())
+    }
+
+    /// Prevenção: os recursos do despacho viram aquisições do roteiro no início,
+    /// mantidas até o fim (para não serem readquiridos fora de ordem após uma
+    /// preempção), e o roteiro é verificado contra a ordem global. Retorna
+    /// `false` se o processo foi rejeitado.
+    fn aplicar_ordem_recursos(&mut self, processo: &mut Processo, violacao: ViolacaoOrdem) -> bool {
+        let mut iniciais: Vec<(Recurso, u32)> = processo.recursos_necessarios.drain().collect();
+        iniciais.sort_by_key(|(r, _)| std::cmp::Reverse(self.posto_recurso(r)));
+        for (recurso, quantidade) in iniciais {
+            processo.roteiro.insert(0, PassoRoteiro { progresso: 0, acao: AcaoRecurso::Adquirir, recurso, quantidade });
+        }
+
+        let (j, i) = match deadlock::violacao_de_ordem(&processo.roteiro, |r| self.posto_recurso(r)) {
+            Some(violacao) => violacao,
+            None => return true,
+        };
+        let rejeitado = violacao == ViolacaoOrdem::Rejeitar;
+        self.estatisticas.violacoes_ordem += 1;
+        self.registrar_evento(Evento::ViolacaoOrdemRecursos {
+            tempo: self.tempo_global,
+            processo: processo.id,
+            pedido: processo.roteiro[j].recurso,
+            possuido: processo.roteiro[i].recurso,
+            rejeitado,
+        });
+        if !rejeitado {
+            deadlock::reordenar_roteiro(&mut processo.roteiro, |r| self.posto_recurso(r));
+        }
+        !rejeitado
+    }
+
+    pub fn memoria_livre(&self) -> u32 {
+        self.recursos_disponiveis.get(&Recurso::Memoria).copied().unwrap_or(0)
+    }
+
+    /// Capacidade do pool de memória: o livre mais o alocado aos processos
+    pub fn memoria_total(&self) -> u32 {
+        self.recursos_totais().get(&Recurso::Memoria).copied().unwrap_or(0)
+    }
+
+    pub fn memoria_em_uso(&self) -> u32 {
+        self.memoria_total() - self.memoria_livre()
+    }
+
+    /// Todos os processos presentes no sistema: em execução, prontos e bloqueados
+    fn todos_processos(&self) -> impl Iterator<Item = &Processo> {
+        self.nucleos.iter().filter_map(|n| n.processo_atual.as_ref())
+            .chain(self.processos_prontos())
+            .chain(self.processos_bloqueados.iter())
+            .chain(self.objetos_sincronizacao.iter().flat_map(|o| o.fila.iter()))
+    }
+
+    /// Capacidade total de cada recurso: o disponível mais o que está alocado
+    pub fn recursos_totais(&self) -> HashMap<Recurso, u32> {
+        let mut totais = self.recursos_disponiveis.clone();
+        for processo in self.todos_processos() {
+            for (recurso, &alocado) in &processo.recursos_alocados {
+                *totais.entry(*recurso).or_insert(0) += alocado;
+            }
+        }
+        totais
+    }
+
+    /// Algoritmo do banqueiro: conceder `pedido` a `processo` deixa o sistema em
+    /// estado seguro, isto é, existe uma ordem em que todos os processos
+    /// conseguem obter a necessidade restante e terminar?
+    pub fn concessao_segura(&self, processo: &Processo, pedido: &HashMap<Recurso, u32>) -> bool {
+        let mut trabalho = self.recursos_disponiveis.clone();
+        for (recurso, &quantidade) in pedido {
+            match trabalho.get_mut(recurso) {
+                Some(disponivel) if *disponivel >= quantidade => *disponivel -= quantidade,
+                _ => return false,
+            }
+        }
+
+        let mut solicitante = processo.clone();
+        for (recurso, &quantidade) in pedido {
+            *solicitante.recursos_alocados.entry(*recurso).or_insert(0) += quantidade;
+        }
+
+        // (alocado, necessidade restante) de cada processo
+        let mut pendentes: Vec<(HashMap<Recurso, u32>, HashMap<Recurso, u32>)> = self.todos_processos()
+            .filter(|p| p.id != processo.id)
+            .chain(std::iter::once(&solicitante))
+            .map(|p| (p.recursos_alocados.clone(), p.necessidade_restante()))
+            .collect();
+
+        while !pendentes.is_empty() {
+            let posicao = pendentes.iter().position(|(_, necessidade)| {
+                necessidade.iter().all(|(r, &q)| trabalho.get(r).copied().unwrap_or(0) >= q)
+            });
+            match posicao {
+                Some(i) => {
+                    let (alocado, _) = pendentes.swap_remove(i);
+                    for (recurso, quantidade) in alocado {
+                        *trabalho.entry(recurso).or_insert(0) += quantidade;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// O pedido está disponível e, no modo de evitação, concedê-lo é seguro
+    fn pode_conceder(&self, processo: &Processo, pedido: &HashMap<Recurso, u32>) -> bool {
+        let disponivel = pedido.iter()
+            .all(|(r, &q)| self.recursos_disponiveis.get(r).is_some_and(|&d| d >= q));
+        disponivel && (self.tratamento_deadlock != TratamentoDeadlock::Evitacao ||
+            self.concessao_segura(processo, pedido))
+    }
+
+    /// Executa os passos do roteiro que o progresso do processo já alcançou.
+    /// Retorna `false` se uma aquisição não pôde ser concedida; o pedido fica
+    /// em `pedido_pendente` e o processo deve ser bloqueado.
+    fn executar_roteiro(&mut self, processo: &mut Processo) -> bool {
+        while let Some(passo) = processo.roteiro.get(processo.passo_roteiro).copied() {
+            if passo.progresso > processo.progresso() {
+                break;
+            }
+            match passo.acao {
+                AcaoRecurso::Adquirir => {
+                    let pedido = HashMap::from([(passo.recurso, passo.quantidade)]);
+                    if !self.pode_conceder(processo, &pedido) {
+                        processo.pedido_pendente = Some((passo.recurso, passo.quantidade));
+                        return false;
+                    }
+                    self.conceder_pedido_roteiro(processo, passo.recurso, passo.quantidade);
+                }
+                AcaoRecurso::Liberar => {
+                    let alocado = processo.recursos_alocados.entry(passo.recurso).or_insert(0);
+                    let quantidade = passo.quantidade.min(*alocado);
+                    *alocado -= quantidade;
+                    if *alocado == 0 {
+                        processo.recursos_alocados.remove(&passo.recurso);
+                    }
+                    *self.recursos_disponiveis.entry(passo.recurso).or_insert(0) += quantidade;
+                    println!("[T={}] Processo {} liberou {} x{}",
+                             self.tempo_global, processo.id, passo.recurso, quantidade);
+                }
+            }
+            processo.passo_roteiro += 1;
+        }
+        true
+    }
+
+    /// Executa as operações de sincronização que o progresso já alcançou;
+    /// devolve o objeto em cuja fila o processo deve esperar
+    fn executar_sincronizacao(&mut self, processo: &mut Processo) -> Option<usize> {
+        while let Some(passo) = processo.roteiro_sincronizacao.get(processo.passo_sincronizacao).copied() {
+            if passo.progresso > processo.progresso() {
+                break;
+            }
+            // Processos que não passaram pela admissão podem trazer operações inválidas
+            if !self.operacao_valida(passo.operacao) {
+                println!("[T={}] Processo {}: {} no objeto #{} ignorado (objeto inválido)",
+                         self.tempo_global, processo.id, passo.operacao, passo.operacao.objeto());
+                processo.passo_sincronizacao += 1;
+                continue;
+            }
+            match passo.operacao {
+                OperacaoSincronizacao::Esperar(semaforo) => match &mut self.objetos_sincronizacao[semaforo].tipo {
+                    TipoSincronizacao::Semaforo { valor } if *valor > 0 => *valor -= 1,
+                    _ => return Some(semaforo),
+                },
+                OperacaoSincronizacao::Sinalizar(semaforo) => match self.objetos_sincronizacao[semaforo].fila.pop_front() {
+                    Some(acordado) => self.acordar(acordado),
+                    None => if let TipoSincronizacao::Semaforo { valor } = &mut self.objetos_sincronizacao[semaforo].tipo {
+                        *valor += 1;
+                    },
+                },
+                OperacaoSincronizacao::Travar(mutex) => match &mut self.objetos_sincronizacao[mutex].tipo {
+                    TipoSincronizacao::Mutex { dono } if dono.is_none_or(|d| d == processo.id) =>
+                        *dono = Some(processo.id),
+                    _ => return Some(mutex),
+                },
+                OperacaoSincronizacao::Destravar(mutex) => self.destravar(mutex, processo.id),
+                OperacaoSincronizacao::AguardarCondicao(condicao) => {
+                    // O passo só avança quando o processo recuperar o mutex
+                    if let Some(mutex) = self.mutex_da_condicao(condicao) {
+                        self.destravar(mutex, processo.id);
+                    }
+                    return Some(condicao);
+                }
+                OperacaoSincronizacao::SinalizarCondicao(condicao) => {
+                    if let Some(acordado) = self.objetos_sincronizacao[condicao].fila.pop_front() {
+                        self.readquirir_mutex(condicao, acordado);
+                    }
+                }
+                OperacaoSincronizacao::DifundirCondicao(condicao) => {
+                    while let Some(acordado) = self.objetos_sincronizacao[condicao].fila.pop_front() {
+                        self.readquirir_mutex(condicao, acordado);
+                    }
+                }
+            }
+            processo.passo_sincronizacao += 1;
+        }
+        None
+    }
+
+    /// Mutex que protege a condição, se ela existir e estiver associada a um mutex
+    fn mutex_da_condicao(&self, condicao: usize) -> Option<usize> {
+        match self.objetos_sincronizacao.get(condicao)?.tipo {
+            TipoSincronizacao::Condicao { mutex } => match self.objetos_sincronizacao.get(mutex)?.tipo {
+                TipoSincronizacao::Mutex { .. } => Some(mutex),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Libera o mutex, entregando-o diretamente ao primeiro processo da fila
+    fn destravar(&mut self, mutex: usize, processo: u32) {
+        let objeto = &mut self.objetos_sincronizacao[mutex];
+        match &mut objeto.tipo {
+            TipoSincronizacao::Mutex { dono } if *dono == Some(processo) => {
+                let proximo = objeto.fila.pop_front();
+                *dono = proximo.as_ref().map(|p| p.id);
+                if let Some(proximo) = proximo {
+                    self.acordar(proximo);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Processo sinalizado na condição volta a disputar o mutex dela
+    fn readquirir_mutex(&mut self, condicao: usize, processo: Processo) {
+        let mutex = match self.mutex_da_condicao(condicao) {
+            Some(mutex) => mutex,
+            None => return self.acordar(processo),
+        };
+        let objeto = &mut self.objetos_sincronizacao[mutex];
+        match &mut objeto.tipo {
+            TipoSincronizacao::Mutex { dono } if dono.is_none() => {
+                *dono = Some(processo.id);
+                self.acordar(processo);
+            }
+            _ => objeto.fila.push_back(processo),
+        }
+    }
+
+    /// Conclui a operação em que o processo esperava e o devolve à fila de prontos
+    fn acordar(&mut self, processo: Processo) {
+        let mut processo = processo;
+        processo.passo_sincronizacao += 1;
+        processo.estado = EstadoProcesso::Pronto;
+        println!("[T={}] Processo {} acordado", self.tempo_global, processo.id);
+        self.escalonador.ao_desbloquear(&processo, self.tempo_global);
+        self.fila_destino(&processo, None).push_back(processo);
+    }
+
+    /// Recalcula a prioridade herdada de cada processo: na herança, o dono de um
+    /// mutex assume a maior prioridade entre os que esperam por ele (seguindo
+    /// as cadeias de espera); no teto, assume o teto de cada mutex que possui
+    fn atualizar_prioridades(&mut self) {
+        if self.protocolo_prioridade == ProtocoloPrioridade::Nenhum {
+            return;
+        }
+        let mut efetivas: HashMap<u32, i32> = self.todos_processos().map(|p| (p.id, p.prioridade)).collect();
+        let mutexes: Vec<(usize, u32, Vec<u32>)> = self.objetos_sincronizacao.iter().enumerate()
+            .filter_map(|(indice, objeto)| match objeto.tipo {
+                TipoSincronizacao::Mutex { dono: Some(dono) } =>
+                    Some((indice, dono, objeto.fila.iter().map(|p| p.id).collect())),
+                _ => None,
+            })
+            .collect();
+
+        match self.protocolo_prioridade {
+            ProtocoloPrioridade::Teto => {
+                for &(indice, dono, _) in &mutexes {
+                    if let (Some(teto), Some(efetiva)) = (self.teto_prioridade(indice), efetivas.get_mut(&dono)) {
+                        *efetiva = (*efetiva).max(teto);
+                    }
+                }
+            }
+            ProtocoloPrioridade::Heranca => {
+                let mut mudou = true;
+                while mudou {
+                    mudou = false;
+                    for (_, dono, fila) in &mutexes {
+                        let maior = fila.iter().filter_map(|id| efetivas.get(id)).copied().max();
+                        if let (Some(maior), Some(efetiva)) = (maior, efetivas.get_mut(dono)) {
+                            if maior > *efetiva {
+                                *efetiva = maior;
+                                mudou = true;
+                            }
+                        }
+                    }
+                }
+            }
+            ProtocoloPrioridade::Nenhum => {}
+        }
+
+        let tempo = self.tempo_global;
+        let mut elevacoes = 0;
+        for processo in self.todos_processos_mut() {
+            let herdada = efetivas.get(&processo.id).copied().filter(|&e| e > processo.prioridade);
+            if herdada == processo.prioridade_herdada {
+                continue;
+            }
+            match herdada {
+                Some(prioridade) => {
+                    println!("[T={}] Processo {} com prioridade elevada para {}", tempo, processo.id, prioridade);
+                    elevacoes += 1;
+                }
+                None => println!("[T={}] Processo {} volta à prioridade base {}", tempo, processo.id, processo.prioridade),
+            }
+            processo.prioridade_herdada = herdada;
+        }
+        self.estatisticas.elevacoes_prioridade += elevacoes;
+    }
+
+    /// Conta o passo como inversão de prioridade se o dono de um mutex disputado
+    /// não está executando enquanto executa alguém menos prioritário que quem espera
+    fn registrar_inversao(&mut self) {
+        let executando: Vec<&Processo> = self.nucleos.iter().filter_map(|n| n.processo_atual.as_ref()).collect();
+        let inversao = self.objetos_sincronizacao.iter().any(|objeto| match objeto.tipo {
+            TipoSincronizacao::Mutex { dono: Some(dono) } if executando.iter().all(|p| p.id != dono) =>
+                objeto.fila.iter().any(|esperando| {
+                    executando.iter().any(|p| p.prioridade_efetiva() < esperando.prioridade_efetiva())
+                }),
+            _ => false,
+        });
+        if inversao {
+            self.estatisticas.passos_inversao += 1;
+        }
+    }
+
+    /// Mutexes de um processo que terminou são liberados para não travar os demais
+    fn liberar_mutexes(&mut self, processo: u32) {
+        for indice in 0..self.objetos_sincronizacao.len() {
+            if self.objetos_sincronizacao[indice].tipo == (TipoSincronizacao::Mutex { dono: Some(processo) }) {
+                self.destravar(indice, processo);
+            }
+        }
+    }
+
+    fn conceder_pedido_roteiro(&mut self, processo: &mut Processo, recurso: Recurso, quantidade: u32) {
+        *self.recursos_disponiveis.get_mut(&recurso).unwrap() -= quantidade;
+        *processo.recursos_alocados.entry(recurso).or_insert(0) += quantidade;
+        println!("[T={}] Processo {} adquiriu {} x{}", self.tempo_global, processo.id, recurso, quantidade);
+    }
+
+    /// Devolve os recursos concedidos no despacho; o que veio do roteiro continua alocado
+    fn liberar_recursos_despacho(&mut self, processo: &mut Processo) {
+        for (recurso, &necessario) in &processo.recursos_necessarios {
+            if let Some(alocado) = processo.recursos_alocados.get_mut(recurso) {
+                let quantidade = necessario.min(*alocado);
+                *alocado -= quantidade;
+                *self.recursos_disponiveis.entry(*recurso).or_insert(0) += quantidade;
+            }
+        }
+        processo.recursos_alocados.retain(|_, q| *q > 0);
+    }
+
+    /// Detecta deadlock pelo grafo de espera, devolvendo os processos
+    /// envolvidos e os ciclos de espera (`None` se não houver deadlock)
+    pub fn verificar_deadlock(&self) -> Option<Deadlock> {
+        deadlock::detectar(self.todos_processos(), &self.recursos_disponiveis)
+    }
+
+    /// Aplica a estratégia de recuperação até não haver mais deadlock
+    pub fn recuperar_deadlock(&mut self, deadlock: Deadlock) {
+        let mut deadlock = deadlock;
+        let criterio = match self.recuperacao_deadlock {
+            RecuperacaoDeadlock::EncerrarTodos => {
+                let motivo = MotivoAborto::DeadlockTodos { processos: deadlock.processos.clone() };
+                for &id in &deadlock.processos {
+                    self.abortar(id, motivo.clone());
+                }
+                return;
+            }
+            RecuperacaoDeadlock::EncerrarPorCusto(criterio) |
+            RecuperacaoDeadlock::PreemptarERetroceder(criterio) => criterio,
+        };
+
+        while let Some(vitima) = deadlock::escolher_vitima(self.processos_bloqueados.iter(), &deadlock, criterio) {
+            if let RecuperacaoDeadlock::PreemptarERetroceder(_) = self.recuperacao_deadlock {
+                self.reverter(vitima);
+            } else {
+                let motivo = MotivoAborto::VitimaDeadlock { criterio, processos: deadlock.processos.clone() };
+                self.abortar(vitima, motivo);
+            }
+            match self.verificar_deadlock() {
+                Some(restante) => deadlock = restante,
+                None => break,
+            }
+        }
+    }
+
+    /// Encerra um processo bloqueado, devolve seus recursos e registra o motivo
+    fn abortar(&mut self, id: u32, motivo: MotivoAborto) {
+        let posicao = match self.processos_bloqueados.iter().position(|p| p.id == id) {
+            Some(posicao) => posicao,
+            None => return,
+        };
+        let mut processo = self.processos_bloqueados.remove(posicao);
+        self.liberar_recursos(&processo);
+        self.liberar_paginas(id);
+        self.liberar_mutexes(id);
+        self.estatisticas.trabalho_desperdicado += processo.progresso();
+        processo.recursos_alocados.clear();
+        processo.pedido_pendente = None;
+        processo.estado = EstadoProcesso::Abortado;
+        self.escalonador.ao_concluir(&processo, self.tempo_global);
+        self.registrar_evento(Evento::ProcessoAbortado { tempo: self.tempo_global, processo: id, motivo: motivo.clone() });
+        self.processos_abortados.push(ProcessoAbortado { processo, tempo: self.tempo_global, motivo });
+    }
+
+    /// Preempta os recursos que outros processos bloqueados aguardam e retrocede
+    /// o processo ao checkpoint mais recente em que não os possuía (ou ao início)
+    fn reverter(&mut self, id: u32) {
+        let posicao = match self.processos_bloqueados.iter().position(|p| p.id == id) {
+            Some(posicao) => posicao,
+            None => return,
+        };
+        let mut processo = self.processos_bloqueados.remove(posicao);
+        let aguardados: Vec<Recurso> = self.processos_bloqueados.iter()
+            .flat_map(|p| p.pedido_em_espera().into_keys())
+            .collect();
+
+        // Só serve um checkpoint cujos recursos o processo ainda tem e que ninguém aguarda
+        let valido = |c: &Checkpoint| c.recursos.iter().all(|(r, &q)| {
+            !aguardados.contains(r) && processo.recursos_alocados.get(r).is_some_and(|&a| a >= q)
+        });
+        let indice = processo.checkpoints.iter().rposition(valido);
+        let checkpoint = match indice {
+            Some(i) => {
+                processo.checkpoints.truncate(i + 1);
+                processo.checkpoints[i].clone()
+            }
+            None => {
+                processo.checkpoints.clear();
+                Checkpoint {
+                    tempo: processo.tempo_chegada,
+                    tempo_restante: processo.tempo_total,
+                    passo_roteiro: 0,
+                    recursos: HashMap::new(),
+                }
+            }
+        };
+
+        for (recurso, alocado) in processo.recursos_alocados.drain() {
+            let mantido = checkpoint.recursos.get(&recurso).copied().unwrap_or(0);
+            *self.recursos_disponiveis.entry(recurso).or_insert(0) += alocado - mantido;
+        }
+        let progresso_perdido = checkpoint.tempo_restante - processo.tempo_restante;
+        processo.recursos_alocados = checkpoint.recursos;
+        processo.tempo_restante = checkpoint.tempo_restante;
+        processo.passo_roteiro = checkpoint.passo_roteiro;
+        processo.pedido_pendente = None;
+        processo.reversoes += 1;
+        processo.estado = EstadoProcesso::Pronto;
+        self.estatisticas.reversoes += 1;
+        self.estatisticas.trabalho_desperdicado += progresso_perdido;
+        self.registrar_evento(Evento::ProcessoRevertido { tempo: self.tempo_global, processo: id, progresso_perdido });
+        self.escalonador.ao_desbloquear(&processo, self.tempo_global);
+        self.fila_destino(&processo, None).push_back(processo);
+    }
+
+    fn alocar_recursos(&mut self, processo: &Processo) -> bool {
+        for (recurso, &necessario) in &processo.recursos_necessarios {
+            let disponivel = self.recursos_disponiveis.get(recurso).unwrap_or(&0);
+            if necessario > *disponivel {
+                return false;
+            }
+        }
+
+        for (recurso, &necessario) in &processo.recursos_necessarios {
+            *self.recursos_disponiveis.get_mut(recurso).unwrap() -= necessario;
+        }
+
+        true
+    }
+
+    fn liberar_recursos(&mut self, processo: &Processo) {
+        for (recurso, &alocado) in &processo.recursos_alocados {
+            *self.recursos_disponiveis.entry(*recurso).or_insert(0) += alocado;
+        }
+    }
+
+    /// Faz a referência de memória da próxima unidade do processo; `false` indica falta de página
+    fn acessar_memoria(&mut self, processo: &Processo) -> bool {
+        let pagina = match processo.pagina_atual() {
+            Some(pagina) => pagina,
+            None => return true,
+        };
+        let mut memoria_virtual = match self.memoria_virtual.take() {
+            Some(memoria_virtual) => memoria_virtual,
+            None => return true,
+        };
+        let acerto = if memoria_virtual.politica == PoliticaSubstituicao::Otima {
+            // Referências que cada processo ainda fará no job atual
+            let futuras: HashMap<u32, (&[u32], usize, usize)> = self.todos_processos()
+                .chain(std::iter::once(processo))
+                .map(|p| {
+                    let inicio = p.progresso() as usize + usize::from(p.id == processo.id);
+                    (p.id, (&p.referencias_paginas[..], inicio, p.tempo_total as usize))
+                })
+                .collect();
+            memoria_virtual.acessar(processo.id, pagina, |dono, pagina| {
+                futuras.get(&dono).and_then(|&(referencias, inicio, fim)| memoria::proximo_uso(referencias, inicio, fim, pagina))
+            })
+        } else {
+            memoria_virtual.acessar(processo.id, pagina, |_, _| None)
+        };
+        self.memoria_virtual = Some(memoria_virtual);
+        acerto
+    }
+
+    fn liberar_paginas(&mut self, processo: u32) {
+        if let Some(memoria_virtual) = &mut self.memoria_virtual {
+            memoria_virtual.liberar_processo(processo);
+        }
+    }
+
+    pub fn escalonar(&mut self) {
+        self.admitir_chegadas();
+        self.liberar_jobs_periodicos();
+
+        // Verificação de deadlock (sem problemas de borrowing)
+        let deadlock = if self.tratamento_deadlock == TratamentoDeadlock::Deteccao &&
+            self.tempo_global.is_multiple_of(10) {
+            self.verificar_deadlock()
+        } else {
+            None
+        };
+        if let Some(deadlock) = deadlock {
+            self.estatisticas.deadlocks_detectados += 1;
+            self.registrar_evento(Evento::DeadlockDetectado { tempo: self.tempo_global, deadlock: deadlock.clone() });
+            self.recuperar_deadlock(deadlock);
+        }
+
+        // Fase 0: passos do roteiro de recursos de quem está executando
+        for indice in 0..self.nucleos.len() {
+            if self.nucleos[indice].atraso_restante > 0 {
+                continue;
+            }
+            let mut processo = match self.nucleos[indice].processo_atual.take() {
+                Some(processo) => processo,
+                None => continue,
+            };
+            if processo.tempo_restante == 0 && processo.es_pendente().is_none() {
+                self.nucleos[indice].processo_atual = Some(processo);
+                continue;
+            }
+            let mut objeto_espera = None;
+            if !self.executar_roteiro(&mut processo) {
+                if let Some((recurso, quantidade)) = processo.pedido_pendente {
+                    println!("[T={}] Processo {} bloqueado aguardando {} x{}",
+                             self.tempo_global, processo.id, recurso, quantidade);
+                }
+            } else if let Some(objeto) = self.executar_sincronizacao(&mut processo) {
+                objeto_espera = Some(objeto);
+            } else if let Some((dispositivo, duracao)) = processo.es_pendente() {
+                match self.dispositivos.get_mut(&dispositivo) {
+                    // Aguarda na fila do dispositivo até o pedido ser atendido
+                    Some(disco) => {
+                        let cilindro = processo.cilindro_es(disco.cilindros);
+                        disco.enfileirar(PedidoDisco {
+                            processo: processo.id, cilindro, chegada: self.tempo_global, duracao,
+                        });
+                        println!("[T={}] Processo {} pediu E/S no dispositivo {} (cilindro {})",
+                                 self.tempo_global, processo.id, dispositivo, cilindro);
+                    }
+                    None => {
+                        let desbloqueio = self.tempo_global + duracao.max(1) - 1;
+                        processo.bloqueado_ate = Some(desbloqueio);
+                        println!("[T={}] Processo {} em E/S no dispositivo {} até T={}",
+                                 self.tempo_global, processo.id, dispositivo, desbloqueio + 1);
+                    }
+                }
+                processo.dispositivo_es = Some(dispositivo);
+                processo.es_realizadas += 1;
+                self.estatisticas.operacoes_es += 1;
+                self.estatisticas.passos_es += duracao;
+            } else if !self.acessar_memoria(&processo) {
+                // A página é carregada agora e fica disponível após a latência
+                let latencia = self.memoria_virtual.as_ref().map_or(1, |m| m.latencia_falta.max(1));
+                let desbloqueio = self.tempo_global + latencia - 1;
+                processo.bloqueado_ate = Some(desbloqueio);
+                processo.faltas_pagina += 1;
+                let pagina = processo.pagina_atual().unwrap_or(0);
+                self.registrar_evento(Evento::FaltaPagina {
+                    tempo: self.tempo_global, processo: processo.id, pagina, desbloqueio: desbloqueio + 1,
+                });
+            } else {
+                self.nucleos[indice].processo_atual = Some(processo);
+                continue;
+            }
+
+            // Bloqueia mantendo o que o roteiro já adquiriu (hold-and-wait)
+            self.liberar_recursos_despacho(&mut processo);
+            processo.estado = EstadoProcesso::Bloqueado;
+            self.escalonador.ao_bloquear(&processo, self.tempo_global);
+            match objeto_espera {
+                Some(objeto) => {
+                    let objeto = &mut self.objetos_sincronizacao[objeto];
+                    println!("[T={}] Processo {} aguardando em {}", self.tempo_global, processo.id, objeto.nome);
+                    objeto.bloqueios += 1;
+                    objeto.fila.push_back(processo);
+                }
+                None => self.processos_bloqueados.push(processo),
+            }
+        }
+        self.atualizar_prioridades();
+
+        // Fase 1: Processar núcleos
+        let mut processos_concluidos = Vec::new();
         let mut processos_preemptados = Vec::new();
 
-        for nucleo in &mut self.nucleos {
+        let intervalo_checkpoint = self.intervalo_checkpoint;
+        for (indice, nucleo) in self.nucleos.iter_mut().enumerate() {
             if let Some(processo) = nucleo.processo_atual.take() {
                 if processo.tempo_restante == 0 {
                     processos_concluidos.push(processo);
+                } else if nucleo.atraso_restante > 0 {
+                    // Núcleo saindo do sono ou aquecendo a cache: o processo espera sem progredir
+                    nucleo.atraso_restante -= 1;
+                    nucleo.tempo_ativo += 1;
+                    nucleo.processo_atual = Some(processo);
                 } else {
                     let mut processo = processo;
-                    processo.tempo_restante -= 1;
+                    let unidades = nucleo.unidades_no_passo().min(processo.tempo_restante);
+                    processo.tempo_restante -= unidades;
+                    processo.tempo_no_quantum += 1;
+                    nucleo.trabalho_realizado += unidades;
+                    nucleo.tempo_ativo += 1;
+
+                    let ultimo = processo.checkpoints.last().map_or(processo.tempo_total, |c| c.tempo_restante);
+                    if intervalo_checkpoint > 0 && ultimo - processo.tempo_restante >= intervalo_checkpoint {
+                        processo.registrar_checkpoint(self.tempo_global);
+                        self.estatisticas.checkpoints += 1;
+                    }
 
-                    if self.algoritmo == AlgoritmoEscalonamento::RoundRobin &&
-                        (self.tempo_global - processo.tempo_chegada) % self.quantum == 0 {
+                    if self.escalonador.deve_preemptar(&processo, self.tempo_global) {
                         processo.estado = EstadoProcesso::Pronto;
-                        processos_preemptados.push(processo);
+                        processos_preemptados.push((indice, processo));
                     } else {
                         processo.estado = EstadoProcesso::Executando;
                         nucleo.processo_atual = Some(processo);
@@ -254,397 +1939,1523 @@ impl Sistema {
         for processo in processos_concluidos {
             println!("[T={}] Processo {} concluído", self.tempo_global, processo.id);
             self.liberar_recursos(&processo);
+            self.liberar_paginas(processo.id);
+            self.liberar_mutexes(processo.id);
+            self.escalonador.ao_concluir(&processo, self.tempo_global);
+            self.estatisticas.processos_concluidos += 1;
+            let retorno = self.tempo_global - processo.tempo_chegada;
+            if processo.limitado_por_es() {
+                self.estatisticas.limitados_es.registrar(retorno, processo.espera_acumulada);
+            } else {
+                self.estatisticas.limitados_cpu.registrar(retorno, processo.espera_acumulada);
+            }
+            if processo.periodo.is_some() {
+                let mut processo = processo;
+                processo.recursos_alocados.clear();
+                processo.estado = EstadoProcesso::Concluido;
+                self.agendar_proximo_job(processo);
+            }
+        }
+        self.atualizar_prioridades();
+
+        // Preempções decididas pela política com visão da fila inteira
+        // (no modo de filas por núcleo, cada núcleo só enxerga a própria fila)
+        let nucleos_preemptados = if self.posicionamento.is_none() {
+            let executando: Vec<Option<&Processo>> = self.nucleos.iter()
+                .map(|n| n.processo_atual.as_ref())
+                .collect();
+            self.escalonador.selecionar_preempcoes(&self.processos, &executando, self.tempo_global)
+        } else {
+            let mut nucleos_preemptados = Vec::new();
+            for (indice, nucleo) in self.nucleos.iter().enumerate() {
+                let preempcoes = self.escalonador.selecionar_preempcoes(
+                    &nucleo.fila_local, &[nucleo.processo_atual.as_ref()], self.tempo_global);
+                if !preempcoes.is_empty() {
+                    nucleos_preemptados.push(indice);
+                }
+            }
+            nucleos_preemptados
+        };
+        for indice in nucleos_preemptados {
+            if let Some(mut processo) = self.nucleos.get_mut(indice).and_then(|n| n.processo_atual.take()) {
+                processo.estado = EstadoProcesso::Pronto;
+                processos_preemptados.push((indice, processo));
+            }
         }
 
         // Recolocar processos preemptados
-        for processo in processos_preemptados {
+        for (indice, processo) in processos_preemptados {
             println!("[T={}] Processo {} preemptado", self.tempo_global, processo.id);
-            // Liberar recursos do processo preemptado
-            self.liberar_recursos(&processo);
-            // Criar uma cópia sem recursos alocados para a fila
-            let mut processo_sem_recursos = processo;
-            processo_sem_recursos.recursos_alocados.clear();
-            self.processos.push_back(processo_sem_recursos);
-        }
-
-        // Fase 2: Atribuir novos processos
-        let mut processos_para_atribuir = Vec::new();
-        
-        // Primeiro, coletamos os processos que podem ser atribuídos
-        for _ in 0..self.nucleos.len() {
-            if let Some(processo) = self.escolher_proximo_processo() {
-                let pode_alocar = processo.recursos_necessarios.iter()
-                    .all(|(r, &q)| self.recursos_disponiveis.get(r).map_or(false, |&d| d >= q));
-                
-                if pode_alocar {
-                    processos_para_atribuir.push(processo.clone());
-                    // Remover o processo da fila
-                    self.processos.pop_front();
-                } else {
-                    // Processo não pode ser alocado, vamos para o próximo
-                    break;
-                }
-            } else {
-                break;
-            }
+            // Liberar os recursos do despacho; os do roteiro continuam com o processo
+            let mut processo = processo;
+            self.liberar_recursos_despacho(&mut processo);
+            self.escalonador.ao_preemptar(&processo, self.tempo_global);
+            self.fila_destino(&processo, Some(indice)).push_back(processo);
         }
 
-        // Agora atribuímos os processos aos núcleos
-        let mut processo_index = 0;
-        for nucleo in &mut self.nucleos {
-            if nucleo.processo_atual.is_none() && processo_index < processos_para_atribuir.len() {
-                let mut processo = processos_para_atribuir[processo_index].clone();
-                
-                // Alocar recursos com verificação de segurança
-                for (recurso, &quantidade) in &processo.recursos_necessarios {
-                    if let Some(disponivel) = self.recursos_disponiveis.get_mut(recurso) {
-                        if *disponivel >= quantidade {
-                            *disponivel -= quantidade;
-                            // Mover recursos para recursos_alocados
-                            processo.recursos_alocados.insert(*recurso, quantidade);
-                        } else {
-                            // Não há recursos suficientes, não alocar
-                            continue;
-                        }
-                    }
+        // Fase 2: Atribuir novos processos aos núcleos ociosos
+        for indice in 0..self.nucleos.len() {
+            while self.nucleos[indice].processo_atual.is_none() {
+                let mut processo = match self.retirar_proximo(indice) {
+                    Some(processo) => processo,
+                    None => break,
+                };
+
+                // Concessão que levaria a estado inseguro é adiada
+                if self.tratamento_deadlock == TratamentoDeadlock::Evitacao &&
+                    self.pode_alocar_recursos(&processo) &&
+                    !self.concessao_segura(&processo, &processo.recursos_necessarios) {
+                    processo.estado = EstadoProcesso::Bloqueado;
+                    self.estatisticas.pedidos_adiados += 1;
+                    let evento = Evento::PedidoAdiado { tempo: self.tempo_global, processo: processo.id };
+                    self.registrar_evento(evento);
+                    self.escalonador.ao_bloquear(&processo, self.tempo_global);
+                    self.processos_bloqueados.push(processo);
+                    continue;
                 }
 
+                // Processo sem recursos suficientes aguarda na fila de bloqueados
+                if !self.alocar_recursos(&processo) {
+                    processo.estado = EstadoProcesso::Bloqueado;
+                    println!("[T={}] Processo {} bloqueado", self.tempo_global, processo.id);
+                    self.escalonador.ao_bloquear(&processo, self.tempo_global);
+                    self.processos_bloqueados.push(processo);
+                    continue;
+                }
+                for (recurso, &quantidade) in &processo.recursos_necessarios {
+                    *processo.recursos_alocados.entry(*recurso).or_insert(0) += quantidade;
+                }
                 processo.estado = EstadoProcesso::Executando;
-                nucleo.processo_atual = Some(processo);
+                processo.tempo_espera = 0;
+                processo.tempo_no_quantum = 0;
+
+                let id_nucleo = self.nucleos[indice].id;
+                let mut aquecimento = 0;
+                if let Some(anterior) = processo.ultimo_nucleo.filter(|&n| n != id_nucleo) {
+                    println!("[T={}] Processo {} migrou do núcleo {} para o núcleo {}",
+                             self.tempo_global, processo.id, anterior, id_nucleo);
+                    processo.migracoes += 1;
+                    aquecimento = self.custo_migracao;
+                    self.estatisticas.migracoes += 1;
+                }
+                processo.ultimo_nucleo = Some(id_nucleo);
+                self.escalonador.ao_despachar(&processo, self.tempo_global);
+
                 println!("[T={}] Núcleo {}: Processo {} iniciado",
-                         self.tempo_global, nucleo.id, nucleo.processo_atual.as_ref().unwrap().id);
-                
-                processo_index += 1;
+                         self.tempo_global, id_nucleo, processo.id);
+                self.nucleos[indice].credito = 0;
+                let latencia = self.nucleos[indice].despertar();
+                if latencia > 0 {
+                    println!("[T={}] Núcleo {} despertando (latência: {} passos)",
+                             self.tempo_global, id_nucleo, latencia);
+                }
+                // O custo da migração atrasa o núcleo sem aumentar o trabalho do processo
+                self.nucleos[indice].atraso_restante += aquecimento;
+                self.nucleos[indice].processo_atual = Some(processo);
             }
         }
 
-        // Processos que não puderam ser atribuídos voltam para a fila
-        for processo in processos_para_atribuir.into_iter().skip(processo_index) {
-            let processo_id = processo.id;
-            let mut processo = processo;
-            processo.estado = EstadoProcesso::Bloqueado;
-            // Liberar recursos se houver algum alocado
-            if !processo.recursos_alocados.is_empty() {
-                self.liberar_recursos(&processo);
-                processo.recursos_alocados.clear();
+        // Dispositivos atendem suas filas; quem teve o pedido concluído é liberado
+        let tempo = self.tempo_global;
+        let atendidos: Vec<u32> = self.dispositivos.values_mut().filter_map(|d| d.passo(tempo)).collect();
+        for id in atendidos {
+            if let Some(processo) = self.processos_bloqueados.iter_mut().find(|p| p.id == id) {
+                processo.bloqueado_ate = Some(tempo);
             }
-            self.processos_bloqueados.push(processo);
-            println!("[T={}] Processo {} bloqueado", self.tempo_global, processo_id);
         }
 
         // Fase 3: Verificar processos bloqueados
         let mut i = 0;
         while i < self.processos_bloqueados.len() {
-            let pode_alocar = self.processos_bloqueados[i].recursos_necessarios.iter()
-                .all(|(r, &q)| self.recursos_disponiveis.get(r).map_or(false, |&d| d >= q));
-
-            if pode_alocar {
-                let processo = self.processos_bloqueados.remove(i);
-                println!("[T={}] Processo {} desbloqueado", self.tempo_global, processo.id);
-                self.processos.push_front(processo);
+            let processo = &self.processos_bloqueados[i];
+            let liberado = match (processo.bloqueado_ate, processo.dispositivo_es) {
+                (Some(desbloqueio), _) => desbloqueio <= self.tempo_global,
+                (None, Some(dispositivo)) if self.dispositivos.contains_key(&dispositivo) => false,
+                _ => {
+                    let pedido = self.processos_bloqueados[i].pedido_em_espera();
+                    self.pode_conceder(&self.processos_bloqueados[i], &pedido)
+                }
+            };
+            if liberado {
+                let mut processo = self.processos_bloqueados.remove(i);
+                processo.bloqueado_ate = None;
+                if let Some((recurso, quantidade)) = processo.pedido_pendente.take() {
+                    self.conceder_pedido_roteiro(&mut processo, recurso, quantidade);
+                    processo.passo_roteiro += 1;
+                }
+                processo.estado = EstadoProcesso::Pronto;
+                match processo.dispositivo_es.take() {
+                    Some(dispositivo) => println!("[T={}] Processo {} concluiu E/S no dispositivo {}",
+                                                  self.tempo_global, processo.id, dispositivo),
+                    None => println!("[T={}] Processo {} desbloqueado", self.tempo_global, processo.id),
+                }
+                self.escalonador.ao_desbloquear(&processo, self.tempo_global);
+                self.fila_destino(&processo, None).push_front(processo);
             } else {
                 i += 1;
             }
         }
 
+        // Envelhecimento: quem ficou na fila de prontos acumula espera
+        let filas_locais = self.nucleos.iter_mut().flat_map(|n| n.fila_local.iter_mut());
+        for processo in self.processos.iter_mut().chain(filas_locais) {
+            processo.tempo_espera += 1;
+            processo.espera_acumulada += 1;
+            self.estatisticas.espera_maxima = self.estatisticas.espera_maxima.max(processo.tempo_espera);
+        }
+
+        for nucleo in &mut self.nucleos {
+            if nucleo.processo_atual.is_none() {
+                nucleo.tempo_ocioso += 1;
+            }
+            if self.posicionamento.is_some() && self.tempo_global.is_multiple_of(INTERVALO_AMOSTRAGEM) {
+                nucleo.historico_fila.push(nucleo.fila_local.len() as u32);
+            }
+            nucleo.contabilizar_energia(self.tempo_global);
+        }
+        if let Some(memoria_virtual) = &mut self.memoria_virtual {
+            memoria_virtual.registrar_passo();
+        }
+
+        self.registrar_inversao();
+        self.verificar_deadlines();
         self.tempo_global += 1;
     }
 
 
-    fn pode_alocar_recursos(&self, processo: &Processo) -> bool {
-        processo.recursos_necessarios.iter()
-            .all(|(r, &q)| self.recursos_disponiveis.get(r).map_or(false, |&d| d >= q))
+    fn pode_alocar_recursos(&self, processo: &Processo) -> bool {
+        processo.recursos_necessarios.iter()
+            .all(|(r, &q)| self.recursos_disponiveis.get(r).is_some_and(|&d| d >= q))
+    }
+
+    /// Consulta a política de escalonamento sem remover o processo da fila
+    pub fn escolher_proximo_processo(&mut self) -> Option<&Processo> {
+        let indice = self.escalonador.escolher_proximo(&self.processos, self.tempo_global)?;
+        self.processos.get(indice)
+    }
+
+    pub fn executar(&mut self, passos: u32) {
+        println!("Iniciando sistema com {} núcleos, algoritmo {}, quantum {}",
+                 self.nucleos.len(), self.escalonador.nome(), self.quantum);
+
+        for _ in 0..passos {
+            if self.quantidade_prontos() == 0 && self.quantidade_bloqueados() == 0 &&
+                self.liberacoes_pendentes.is_empty() && self.chegadas_pendentes.is_empty() &&
+                self.nucleos.iter().all(|n| n.processo_atual.is_none()) {
+                println!("Todos os processos foram concluídos!");
+                break;
+            }
+
+            self.escalonar();
+        }
+
+        println!("Simulação concluída no tempo {}", self.tempo_global);
+        self.mostrar_estatisticas();
+    }
+
+    pub fn mostrar_estatisticas(&self) {
+        println!("\n=== Estatísticas ===");
+        for nucleo in &self.nucleos {
+            println!("Núcleo {}: tempo ocioso = {}", nucleo.id, nucleo.tempo_ocioso);
+        }
+
+        println!("\nRecursos disponíveis:");
+        for (recurso, quantidade) in &self.recursos_disponiveis {
+            match recurso {
+                Recurso::Memoria => println!("  {}: {} MB", recurso, quantidade),
+                _ => println!("  {}: {}", recurso, quantidade),
+            }
+        }
+    }
+
+    pub fn mostrar_estatisticas_detalhadas(&self) {
+        println!("\n=== ESTATÍSTICAS DETALHADAS ===");
+        println!("Tempo global: {}", self.tempo_global);
+        println!("Algoritmo: {}", self.escalonador.nome());
+        println!("Quantum: {}", self.quantum);
+        println!("Taxa de chegada: {} processos/segundo", self.taxa_chegada_processos);
+        
+        println!("\n=== NÚCLEOS ===");
+        for nucleo in &self.nucleos {
+            let status = match &nucleo.processo_atual {
+                Some(processo) => format!("Executando P{}", processo.id),
+                None => "Ocioso".to_string(),
+            };
+            println!("Núcleo {} [{}, {}%]: {} (tempo ocioso: {})",
+                     nucleo.id, nucleo.classe, nucleo.capacidade, status, nucleo.tempo_ocioso);
+        }
+
+        if self.nucleos.iter().any(|n| n.classe != ClasseNucleo::Desempenho) {
+            println!("\n=== CLASSES DE NÚCLEO ===");
+            for classe in [ClasseNucleo::Desempenho, ClasseNucleo::Eficiencia] {
+                let nucleos: Vec<&Nucleo> = self.nucleos.iter().filter(|n| n.classe == classe).collect();
+                let trabalho: u32 = nucleos.iter().map(|n| n.trabalho_realizado).sum();
+                let ativo: u32 = nucleos.iter().map(|n| n.tempo_ativo).sum();
+                let disponivel = nucleos.len() as u32 * self.tempo_global.max(1);
+                println!("{}: {} núcleos, trabalho realizado {}, tempo ativo {}, utilização {:.1}%",
+                         classe, nucleos.len(), trabalho, ativo, ativo as f64 * 100.0 / disponivel as f64);
+            }
+        }
+        
+        println!("\n=== FILAS DE PROCESSOS ===");
+        let prontos = self.quantidade_prontos();
+        println!("Prontos: {} processos", prontos);
+        for (i, processo) in self.processos_prontos().take(5).enumerate() {
+            let deadline = processo.deadline.map(|d| format!(", deadline: {}", d)).unwrap_or_default();
+            println!("  {}. P{} (prioridade: {}, efetiva: {}, espera: {}, tempo restante: {}{})", 
+                     i+1, processo.id, processo.prioridade, self.escalonador.prioridade_efetiva(processo),
+                     processo.tempo_espera, processo.tempo_restante, deadline);
+        }
+        if prontos > 5 {
+            println!("  ... e mais {} processos", prontos - 5);
+        }
+        
+        println!("Bloqueados: {} processos", self.processos_bloqueados.len());
+        for (i, processo) in self.processos_bloqueados.iter().take(3).enumerate() {
+            println!("  {}. P{} (prioridade: {}, tempo restante: {})", 
+                     i+1, processo.id, processo.prioridade, processo.tempo_restante);
+        }
+        if self.processos_bloqueados.len() > 3 {
+            println!("  ... e mais {} processos", self.processos_bloqueados.len() - 3);
+        }
+
+        if !self.chegadas_pendentes.is_empty() {
+            println!("Aguardando chegada: {} processos", self.chegadas_pendentes.len());
+            for processo in self.chegadas_pendentes.iter().take(3) {
+                println!("  P{} chega em T={}", processo.id, processo.tempo_chegada);
+            }
+        }
+        
+        if !self.objetos_sincronizacao.is_empty() {
+            println!("\n=== SINCRONIZAÇÃO ===");
+            println!("Protocolo de prioridade: {} | elevações: {} | passos com inversão: {}",
+                     self.protocolo_prioridade, self.estatisticas.elevacoes_prioridade,
+                     self.estatisticas.passos_inversao);
+            for (indice, objeto) in self.objetos_sincronizacao.iter().enumerate() {
+                let teto = match objeto.tipo {
+                    TipoSincronizacao::Mutex { .. } =>
+                        self.teto_prioridade(indice).map(|t| format!(" | teto: {}", t)).unwrap_or_default(),
+                    _ => String::new(),
+                };
+                println!("  {} | esperas: {}{}", objeto, objeto.bloqueios, teto);
+            }
+        }
+
+        println!("\n=== RECURSOS DISPONÍVEIS ===");
+        for (recurso, quantidade) in &self.recursos_disponiveis {
+            match recurso {
+                Recurso::Memoria => println!("  {}: {} MB", recurso, quantidade),
+                _ => println!("  {}: {}", recurso, quantidade),
+            }
+        }
+
+        println!("\n=== MEMÓRIA ===");
+        let total = self.memoria_total();
+        println!("Em uso: {} MB | Livre: {} MB | Total: {} MB ({:.1}% ocupada)",
+                 self.memoria_em_uso(), self.memoria_livre(), total,
+                 self.memoria_em_uso() as f64 * 100.0 / total.max(1) as f64);
+        let mut consumidores: Vec<(u32, u32)> = self.todos_processos()
+            .filter_map(|p| p.recursos_alocados.get(&Recurso::Memoria).map(|&mb| (p.id, mb)))
+            .filter(|&(_, mb)| mb > 0)
+            .collect();
+        consumidores.sort_by_key(|&(id, mb)| (std::cmp::Reverse(mb), id));
+        for (id, mb) in consumidores.iter().take(3) {
+            println!("  P{}: {} MB", id, mb);
+        }
+
+        if self.estatisticas.operacoes_es > 0 {
+            println!("\n=== CPU x E/S ===");
+            println!("Rajadas de E/S: {} ({} passos)", self.estatisticas.operacoes_es, self.estatisticas.passos_es);
+            let em_es = self.processos_bloqueados.iter().filter(|p| p.dispositivo_es.is_some()).count();
+            println!("Processos em E/S agora: {}", em_es);
+            for (recurso, disco) in &self.dispositivos {
+                println!("Dispositivo {} ({}): cabeçote no cilindro {} de {} | fila: {}",
+                         recurso, disco.algoritmo, disco.posicao, disco.cilindros, disco.fila.len());
+                println!("  Atendidos: {} | utilização: {:.1}% | busca média: {:.1} cilindros | espera média na fila: {:.1}",
+                         disco.atendidos, disco.utilizacao(self.tempo_global), disco.busca_media(), disco.espera_media());
+            }
+            for (nome, resumo) in [("Limitados por CPU", &self.estatisticas.limitados_cpu),
+                                   ("Limitados por E/S", &self.estatisticas.limitados_es)] {
+                println!("{}: {} concluídos | retorno médio: {:.1} | espera média na fila: {:.1}",
+                         nome, resumo.concluidos, resumo.retorno_medio(), resumo.espera_media());
+            }
+        }
+
+        if let Some(memoria_virtual) = &self.memoria_virtual {
+            println!("\n=== MEMÓRIA VIRTUAL ===");
+            println!("Substituição: {} | quadros: {}/{} ocupados | latência da falta: {} passos",
+                     memoria_virtual.politica, memoria_virtual.quadros_ocupados(),
+                     memoria_virtual.quadros.len(), memoria_virtual.latencia_falta);
+            println!("Acessos: {} | faltas: {} ({:.1}%) | substituições: {}",
+                     memoria_virtual.acessos, memoria_virtual.faltas,
+                     memoria_virtual.taxa_faltas() * 100.0, memoria_virtual.substituicoes);
+            println!("Working sets: {} páginas para {} quadros{}",
+                     memoria_virtual.demanda_total(), memoria_virtual.quadros.len(),
+                     if memoria_virtual.em_thrashing() { " -> THRASHING" } else { "" });
+            println!("Passos em thrashing: {} ({:.1}% do tempo)", memoria_virtual.passos_thrashing,
+                     memoria_virtual.passos_thrashing as f64 * 100.0 / self.tempo_global.max(1) as f64);
+            let mut faltas: Vec<(u32, u32)> = self.todos_processos()
+                .filter(|p| p.faltas_pagina > 0)
+                .map(|p| (p.id, p.faltas_pagina))
+                .collect();
+            faltas.sort_by_key(|&(id, f)| (std::cmp::Reverse(f), id));
+            for (id, f) in faltas.iter().take(3) {
+                println!("  P{}: {} faltas (working set: {} páginas)", id, f, memoria_virtual.working_set(*id));
+            }
+        }
+
+        println!("\n=== DESEMPENHO ===");
+        println!("Processos concluídos: {}", self.estatisticas.processos_concluidos);
+        println!("Vazão: {:.3} processos/passo",
+                 self.estatisticas.processos_concluidos as f64 / self.tempo_global.max(1) as f64);
+        println!("Migrações entre núcleos: {} (custo por migração: {} passos)",
+                 self.estatisticas.migracoes, self.custo_migracao);
+        println!("Tratamento de deadlock: {}", self.tratamento_deadlock);
+        match self.tratamento_deadlock {
+            TratamentoDeadlock::Evitacao =>
+                println!("Pedidos adiados (estado inseguro): {}", self.estatisticas.pedidos_adiados),
+            TratamentoDeadlock::Prevencao(_) => {
+                let ordem: Vec<String> = self.ordem_recursos.iter().map(|r| r.to_string()).collect();
+                println!("Ordem global de recursos: {}", ordem.join(" < "));
+                println!("Violações de ordem: {}", self.estatisticas.violacoes_ordem);
+            }
+            TratamentoDeadlock::Deteccao => {}
+        }
+        let espera_atual = self.processos_prontos().map(|p| p.tempo_espera).max().unwrap_or(0);
+        println!("Maior espera na fila (starvation): {} passos (atual: {})",
+                 self.estatisticas.espera_maxima, espera_atual);
+        if self.estatisticas.jobs_liberados > 0 || self.estatisticas.deadlines_perdidas > 0 {
+            println!("Jobs periódicos liberados: {}", self.estatisticas.jobs_liberados);
+            println!("Deadlines perdidas: {}", self.estatisticas.deadlines_perdidas);
+        }
+
+        if self.tratamento_deadlock == TratamentoDeadlock::Deteccao {
+            println!("\n=== DEADLOCK ===");
+            println!("Deadlocks detectados: {}", self.estatisticas.deadlocks_detectados);
+            println!("Recuperação: {}", self.recuperacao_deadlock);
+            println!("Processos abortados: {} | reversões: {}",
+                     self.processos_abortados.len(), self.estatisticas.reversoes);
+            let intervalo = match self.intervalo_checkpoint {
+                0 => "desligados".to_string(),
+                n => format!("a cada {} unidades, {} salvos", n, self.estatisticas.checkpoints),
+            };
+            println!("Trabalho desperdiçado: {} unidades (checkpoints: {})",
+                     self.estatisticas.trabalho_desperdicado, intervalo);
+            for abortado in self.processos_abortados.iter().rev().take(5) {
+                println!("  [T={}] P{}: {}", abortado.tempo, abortado.processo.id, abortado.motivo);
+            }
+            match self.verificar_deadlock() {
+                Some(deadlock) => {
+                    println!("Situação atual: {}", deadlock.processos.iter()
+                        .map(|id| format!("P{}", id)).collect::<Vec<_>>().join(", "));
+                    for ciclo in &deadlock.ciclos {
+                        println!("  Ciclo: {}", Deadlock::descrever_ciclo(ciclo));
+                    }
+                }
+                None => println!("Situação atual: sem deadlock"),
+            }
+            let ultimo = self.eventos.iter().rev()
+                .find(|e| matches!(e, Evento::DeadlockDetectado { .. }));
+            if let Some(evento) = ultimo {
+                println!("Última detecção: {}", evento);
+            }
+        }
+
+        if let Some(politica) = self.posicionamento {
+            println!("\n=== FILAS POR NÚCLEO (posicionamento: {}) ===", politica);
+            println!("Roubos de trabalho: {}", self.estatisticas.roubos);
+            for nucleo in &self.nucleos {
+                let historico = &nucleo.historico_fila;
+                let media = historico.iter().sum::<u32>() as f64 / historico.len().max(1) as f64;
+                let recentes: Vec<String> = historico.iter().rev().take(10).rev()
+                    .map(|t| t.to_string()).collect();
+                println!("Núcleo {}: fila atual {}, média {:.1}, máxima {}, roubos {} | últimas amostras: [{}]",
+                         nucleo.id, nucleo.fila_local.len(), media,
+                         historico.iter().max().unwrap_or(&0), nucleo.roubos, recentes.join(", "));
+            }
+        }
+
+        if self.nucleos.iter().any(|n| n.modelo_energia.is_some()) {
+            println!("\n=== ENERGIA ===");
+            for nucleo in &self.nucleos {
+                if let Some(modelo) = &nucleo.modelo_energia {
+                    println!("Núcleo {} [{}]: {}, frequência {}%, energia {:.2}, trocas de frequência {}, despertares {}",
+                             nucleo.id, modelo.governador, nucleo.estado_energia, nucleo.frequencia(),
+                             nucleo.energia_consumida, nucleo.trocas_frequencia, nucleo.despertares);
+                }
+            }
+            let energia = self.energia_total();
+            println!("Energia total: {:.2} W×passo (potência média: {:.2} W)",
+                     energia, energia / self.tempo_global.max(1) as f64);
+            println!("Produto energia-atraso (EDP): {:.1}", self.produto_energia_atraso());
+            if self.estatisticas.processos_concluidos > 0 {
+                println!("Energia por processo concluído: {:.2}",
+                         energia / self.estatisticas.processos_concluidos as f64);
+            }
+        }
+
+        let relatorio = self.escalonador.relatorio();
+        if !relatorio.is_empty() {
+            println!("\n=== POLÍTICA: {} ===", self.escalonador.nome());
+            for linha in relatorio {
+                println!("{}", linha);
+            }
+        }
+    }
+
+    pub fn escalonar_interativo(&mut self) {
+        // Versão simplificada do escalonar para modo interativo
+        self.escalonar();
+    }
+}
+
+/// Aplica a política apenas aos processos elegíveis da fila e remove o escolhido
+fn escolher_elegivel(escalonador: &mut dyn Escalonador, fila: &mut VecDeque<Processo>,
+                     elegiveis: &[bool], tempo: u32) -> Option<Processo> {
+    if elegiveis.iter().all(|&e| e) {
+        let indice = escalonador.escolher_proximo(fila, tempo)?;
+        return fila.remove(indice);
+    }
+
+    let indices: Vec<usize> = (0..fila.len()).filter(|&i| elegiveis[i]).collect();
+    let candidatos: VecDeque<Processo> = indices.iter().map(|&i| fila[i].clone()).collect();
+    let escolhido = escalonador.escolher_proximo(&candidatos, tempo)?;
+    fila.remove(indices[escolhido])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processo_new() {
+        let processo = Processo::new(1, 100, 5);
+        
+        assert_eq!(processo.id, 1);
+        assert_eq!(processo.prioridade, 5);
+        assert_eq!(processo.tempo_total, 100);
+        assert_eq!(processo.tempo_restante, 100);
+        assert_eq!(processo.tempo_chegada, 0);
+        assert_eq!(processo.estado, EstadoProcesso::Pronto);
+        assert!(processo.recursos_alocados.is_empty());
+        assert!(processo.recursos_necessarios.is_empty());
+    }
+
+    #[test]
+    fn test_processo_necessita_recurso() {
+        let processo = Processo::new(1, 100, 5)
+            .necessita_recurso(Recurso::Impressora, 1)
+            .necessita_recurso(Recurso::Memoria, 512);
+        
+        assert_eq!(processo.recursos_necessarios.get(&Recurso::Impressora), Some(&1));
+        assert_eq!(processo.recursos_necessarios.get(&Recurso::Memoria), Some(&512));
+        assert_eq!(processo.recursos_necessarios.len(), 2);
+    }
+
+    #[test]
+    fn test_nucleo_new() {
+        let nucleo = Nucleo::new(0);
+        
+        assert_eq!(nucleo.id, 0);
+        assert!(nucleo.processo_atual.is_none());
+        assert_eq!(nucleo.tempo_ocioso, 0);
+    }
+
+    #[test]
+    fn test_sistema_new() {
+        let sistema = Sistema::new(4, 10, AlgoritmoEscalonamento::RoundRobin);
+        
+        assert_eq!(sistema.nucleos.len(), 4);
+        assert_eq!(sistema.quantum, 10);
+        assert_eq!(sistema.algoritmo, AlgoritmoEscalonamento::RoundRobin);
+        assert_eq!(sistema.tempo_global, 0);
+        assert_eq!(sistema.taxa_chegada_processos, 20);
+        assert!(sistema.processos.is_empty());
+        assert!(sistema.processos_bloqueados.is_empty());
+        
+        // Verificar recursos iniciais
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Impressora), Some(&2));
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Scanner), Some(&1));
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Disco), Some(&3));
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Memoria), Some(&MEMORIA_TOTAL_MB));
+    }
+
+    #[test]
+    fn test_adicionar_processo() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        let processo = Processo::new(1, 50, 3);
+        
+        sistema.adicionar_processo(processo);
+        
+        assert_eq!(sistema.processos.len(), 1);
+        assert_eq!(sistema.processos[0].id, 1);
+        assert_eq!(sistema.processos[0].estado, EstadoProcesso::Pronto);
+    }
+
+    #[test]
+    fn test_escolher_proximo_processo_round_robin() {
+        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::RoundRobin);
+        
+        let processo1 = Processo::new(1, 30, 1);
+        let processo2 = Processo::new(2, 20, 5);
+        let processo3 = Processo::new(3, 40, 3);
+        
+        sistema.adicionar_processo(processo1);
+        sistema.adicionar_processo(processo2);
+        sistema.adicionar_processo(processo3);
+        
+        let proximo = sistema.escolher_proximo_processo();
+        assert_eq!(proximo.unwrap().id, 1); // Primeiro da fila
+    }
+
+    #[test]
+    fn test_escolher_proximo_processo_prioridade() {
+        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::Prioridade);
+        
+        let processo1 = Processo::new(1, 30, 1);
+        let processo2 = Processo::new(2, 20, 5);
+        let processo3 = Processo::new(3, 40, 3);
+        
+        sistema.adicionar_processo(processo1);
+        sistema.adicionar_processo(processo2);
+        sistema.adicionar_processo(processo3);
+        
+        let proximo = sistema.escolher_proximo_processo();
+        assert_eq!(proximo.unwrap().id, 2); // Maior prioridade (5)
+    }
+
+    #[test]
+    fn test_escolher_proximo_processo_shortest_job_first() {
+        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::ShortestJobFirst);
+        
+        let processo1 = Processo::new(1, 30, 1);
+        let processo2 = Processo::new(2, 20, 5);
+        let processo3 = Processo::new(3, 40, 3);
+        
+        sistema.adicionar_processo(processo1);
+        sistema.adicionar_processo(processo2);
+        sistema.adicionar_processo(processo3);
+        
+        let proximo = sistema.escolher_proximo_processo();
+        assert_eq!(proximo.unwrap().id, 2); // Menor tempo restante (20)
+    }
+
+    #[test]
+    fn test_pode_alocar_recursos_insuficientes() {
+        let sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        let processo = Processo::new(1, 50, 3)
+            .necessita_recurso(Recurso::Impressora, 3) // Só tem 2 disponíveis
+            .necessita_recurso(Recurso::Memoria, 2048);
+        
+        assert!(!sistema.pode_alocar_recursos(&processo));
+    }
+
+    #[test]
+    fn test_alocar_recursos_sucesso() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        let processo = Processo::new(1, 50, 3)
+            .necessita_recurso(Recurso::Impressora, 1)
+            .necessita_recurso(Recurso::Memoria, 2048);
+        
+        let resultado = sistema.alocar_recursos(&processo);
+        assert!(resultado);
+        
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Impressora), Some(&1));
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Memoria), Some(&6144));
+    }
+
+    #[test]
+    fn test_alocar_recursos_falha() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        let processo = Processo::new(1, 50, 3)
+            .necessita_recurso(Recurso::Impressora, 5) // Mais que disponível
+            .necessita_recurso(Recurso::Memoria, 2048);
+        
+        let resultado = sistema.alocar_recursos(&processo);
+        assert!(!resultado);
+        
+        // Recursos devem permanecer inalterados
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Impressora), Some(&2));
+        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Memoria), Some(&MEMORIA_TOTAL_MB));
+    }
+
+    #[test]
+    fn test_escalonar_multiplos_processos() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        
+        let processo1 = Processo::new(1, 3, 1);
+        let processo2 = Processo::new(2, 2, 2);
+        let processo3 = Processo::new(3, 4, 3);
+        
+        sistema.adicionar_processo(processo1);
+        sistema.adicionar_processo(processo2);
+        sistema.adicionar_processo(processo3);
+        
+        // Primeiro passo: dois processos devem ser atribuídos aos dois núcleos
+        sistema.escalonar();
+        
+        let nucleos_ocupados = sistema.nucleos.iter()
+            .filter(|n| n.processo_atual.is_some())
+            .count();
+        assert_eq!(nucleos_ocupados, 2);
+        assert_eq!(sistema.processos.len(), 1); // Um processo ainda na fila
+    }
+
+    #[test]
+    fn test_escalonar_despacha_a_escolha_da_politica() {
+        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::Prioridade);
+        sistema.adicionar_processo(Processo::new(1, 5, 1));
+        sistema.adicionar_processo(Processo::new(2, 5, 5));
+
+        // O processo de maior prioridade não está no início da fila
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 2);
+        assert_eq!(sistema.processos[0].id, 1);
+    }
+
+    #[test]
+    fn test_escalonar_bloqueia_sem_recursos_e_segue() {
+        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::RoundRobin);
+        sistema.adicionar_processo(Processo::new(1, 5, 1).necessita_recurso(Recurso::Impressora, 5));
+        sistema.adicionar_processo(Processo::new(2, 5, 1));
+
+        // Fora do passo de verificação de deadlock, que removeria P1 da fila
+        sistema.tempo_global = 1;
+
+        // Sem impressoras suficientes, P1 é bloqueado e o núcleo fica com P2
+        sistema.escalonar();
+        assert_eq!(sistema.processos_bloqueados[0].id, 1);
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_preempcao_round_robin() {
+        let mut sistema = Sistema::new(1, 2, AlgoritmoEscalonamento::RoundRobin); // Quantum 2
+        
+        let processo1 = Processo::new(1, 5, 1);
+        let processo2 = Processo::new(2, 3, 2);
+        
+        sistema.adicionar_processo(processo1);
+        sistema.adicionar_processo(processo2);
+        
+        // Primeiro processo executa
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
+        
+        // Segundo passo - ainda processo 1
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
+        
+        // Terceiro passo - deve haver preempção (quantum = 2)
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_processo_periodico() {
+        let processo = Processo::new(1, 2, 1).periodico(10);
+        assert_eq!(processo.periodo, Some(10));
+        assert_eq!(processo.deadline_relativa, Some(10));
+
+        let processo = Processo::new(2, 2, 1).com_deadline(4).periodico(10);
+        assert_eq!(processo.deadline_relativa, Some(4));
+    }
+
+    #[test]
+    fn test_liberacao_periodica_e_deadline_perdida() {
+        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::Edf);
+        // Utilização > 1: o segundo processo não consegue cumprir o prazo
+        sistema.adicionar_processo(Processo::new(1, 3, 1).periodico(4));
+        sistema.adicionar_processo(Processo::new(2, 3, 1).periodico(5));
+
+        for _ in 0..12 {
+            sistema.escalonar();
+        }
+
+        assert!(sistema.estatisticas.jobs_liberados > 0);
+        assert!(sistema.estatisticas.deadlines_perdidas > 0);
+        assert!(sistema.eventos.iter().any(|e| matches!(e, Evento::DeadlinePerdida { .. })));
+    }
+
+    #[test]
+    fn test_edf_multinucleo_preempta_so_o_deadline_mais_distante() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::Edf);
+        sistema.adicionar_processo(Processo::new(1, 20, 1).com_deadline(65));
+        sistema.adicionar_processo(Processo::new(2, 20, 1).com_deadline(100));
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().id, 2);
+
+        // P3 vence P2, mas P4 não vence P1: só o núcleo de P2 troca de processo
+        sistema.adicionar_processo(Processo::new(3, 20, 1).com_deadline(59));
+        sistema.adicionar_processo(Processo::new(4, 20, 1).com_deadline(89));
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().id, 3);
+        let na_fila: Vec<u32> = sistema.processos.iter().map(|p| p.id).collect();
+        assert_eq!(na_fila, vec![4, 2]);
+    }
+
+    #[test]
+    fn test_srtf_preempta_ao_chegar_processo_mais_curto() {
+        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::Srtf);
+        sistema.adicionar_processo(Processo::new(1, 10, 1));
+        sistema.escalonar();
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
+
+        sistema.adicionar_processo(Processo::new(2, 2, 1));
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 2);
+        assert_eq!(sistema.processos[0].id, 1);
+    }
+
+    #[test]
+    fn test_envelhecimento_evita_starvation() {
+        let mut sistema = Sistema::new(1, 1, AlgoritmoEscalonamento::Prioridade);
+        sistema.definir_escalonador(Box::new(EscalonadorPrioridade::new(2)));
+        sistema.adicionar_processo(Processo::new(1, 1, 1));
+
+        // Processos de prioridade alta chegando sem parar
+        for id in 2..20 {
+            sistema.adicionar_processo(Processo::new(id, 1, 5));
+            sistema.escalonar();
+            sistema.escalonar();
+        }
+
+        assert!(sistema.processos.iter().all(|p| p.id != 1));
+        assert!(sistema.estatisticas.espera_maxima > 0);
+    }
+
+    #[test]
+    fn test_filas_por_nucleo_posicionamento_round_robin() {
+        let mut sistema = Sistema::new(3, 5, AlgoritmoEscalonamento::RoundRobin);
+        sistema.habilitar_filas_por_nucleo(PoliticaPosicionamento::RoundRobin);
+        for id in 1..=6 {
+            sistema.adicionar_processo(Processo::new(id, 10, 1));
+        }
+
+        assert!(sistema.processos.is_empty());
+        assert!(sistema.nucleos.iter().all(|n| n.fila_local.len() == 2));
+        assert_eq!(sistema.quantidade_prontos(), 6);
+    }
+
+    #[test]
+    fn test_roubo_de_trabalho() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        sistema.habilitar_filas_por_nucleo(PoliticaPosicionamento::RoundRobin);
+        // Todos os processos vão para a fila do núcleo 0
+        for id in 1..=3 {
+            sistema.nucleos[0].fila_local.push_back(Processo::new(id, 10, 1));
+        }
+
+        sistema.escalonar();
+
+        assert!(sistema.nucleos.iter().all(|n| n.processo_atual.is_some()));
+        assert_eq!(sistema.estatisticas.roubos, 1);
+        assert_eq!(sistema.nucleos[1].roubos, 1);
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().id, 3);
+    }
+
+    #[test]
+    fn test_posicionamento_menos_carregado() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        sistema.habilitar_filas_por_nucleo(PoliticaPosicionamento::MenosCarregado);
+        sistema.nucleos[0].fila_local.push_back(Processo::new(1, 10, 1));
+        sistema.adicionar_processo(Processo::new(2, 10, 1));
+
+        assert_eq!(sistema.nucleos[1].fila_local[0].id, 2);
+    }
+
+    #[test]
+    fn test_cfs_por_nucleo_nao_preempta_processo_sozinho() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::Cfs);
+        sistema.habilitar_filas_por_nucleo(PoliticaPosicionamento::RoundRobin);
+        // P1 e P3 disputam o núcleo 0; P2 fica sozinho no núcleo 1
+        for id in 1..=3 {
+            sistema.adicionar_processo(Processo::new(id, 30, 1));
+        }
+
+        let mut executou_no_nucleo_0 = Vec::new();
+        for _ in 0..20 {
+            sistema.escalonar();
+            assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().id, 2);
+            executou_no_nucleo_0.push(sistema.nucleos[0].processo_atual.as_ref().unwrap().id);
+        }
+
+        // A fatia de P2 vence várias vezes, mas ninguém espera pelo núcleo 1
+        assert!(executou_no_nucleo_0.contains(&1) && executou_no_nucleo_0.contains(&3));
+    }
+
+    #[test]
+    fn test_afinidade_rigida() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        sistema.adicionar_processo(Processo::new(1, 10, 1).fixado_em(1));
+        sistema.adicionar_processo(Processo::new(2, 10, 1).fixado_em(1));
+
+        sistema.escalonar();
+
+        assert!(sistema.nucleos[0].processo_atual.is_none());
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().id, 1);
+        assert_eq!(sistema.processos[0].id, 2);
+    }
+
+    #[test]
+    fn test_nucleo_preferido_e_migracao() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        sistema.custo_migracao = 1;
+        sistema.adicionar_processo(Processo::new(1, 10, 1).com_nucleo_preferido(1));
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().id, 1);
+        assert!(sistema.nucleos[0].processo_atual.is_none());
+
+        // Com o núcleo 1 ocupado, um processo que preferia o núcleo 1 migra para o 0
+        let mut processo = Processo::new(2, 10, 1).com_nucleo_preferido(1);
+        processo.ultimo_nucleo = Some(1);
+        sistema.adicionar_processo(processo);
+        sistema.escalonar();
+
+        let processo = sistema.nucleos[0].processo_atual.as_ref().unwrap();
+        assert_eq!(processo.id, 2);
+        assert_eq!(processo.migracoes, 1);
+        assert_eq!(processo.tempo_restante, 10);
+        assert_eq!(sistema.estatisticas.migracoes, 1);
+
+        // O núcleo 0 fica um passo parado antes de P2 progredir
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().tempo_restante, 10);
+        sistema.escalonar();
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().tempo_restante, 9);
+    }
+
+    #[test]
+    fn test_topologia_heterogenea_velocidade() {
+        let topologia = [(ClasseNucleo::Desempenho, 200), (ClasseNucleo::Eficiencia, 50)];
+        let mut sistema = Sistema::com_topologia(&topologia, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.adicionar_processo(Processo::new(1, 20, 1));
+        sistema.adicionar_processo(Processo::new(2, 20, 1));
+
+        for _ in 0..5 {
+            sistema.escalonar();
+        }
+
+        // 4 passos de execução: 8 unidades no núcleo rápido, 2 no lento
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().tempo_restante, 12);
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().tempo_restante, 18);
+        assert_eq!(sistema.nucleos[0].trabalho_realizado, 8);
+        assert_eq!(sistema.nucleos[1].trabalho_realizado, 2);
+    }
+
+    #[test]
+    fn test_posicionamento_consciente_de_energia() {
+        let topologia = [(ClasseNucleo::Desempenho, 100), (ClasseNucleo::Eficiencia, 50)];
+        let mut sistema = Sistema::com_topologia(&topologia, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.posicionamento_energia = Some(PosicionamentoEnergia {
+            limiar_tempo_curto: 5,
+            limiar_prioridade_baixa: 2,
+        });
+        sistema.adicionar_processo(Processo::new(1, 3, 8)); // curto
+        sistema.adicionar_processo(Processo::new(2, 30, 8)); // longo e prioritário
+
+        sistema.escalonar();
+
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 2);
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().id, 1);
+    }
+
+    #[test]
+    fn test_governador_economia_reduz_velocidade_e_energia() {
+        let executar = |governador| {
+            let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::RoundRobin);
+            sistema.habilitar_modelo_energia(governador);
+            sistema.adicionar_processo(Processo::new(1, 4, 1));
+            sistema.executar(50);
+            (sistema.tempo_global, sistema.energia_total())
+        };
+
+        let (tempo_desempenho, energia_desempenho) = executar(Governador::Desempenho);
+        let (tempo_economia, energia_economia) = executar(Governador::Economia);
+
+        // Metade da frequência: o dobro de passos, mas cada passo custa 1/3 da potência
+        assert_eq!(tempo_economia - tempo_desempenho, 4);
+        assert!(energia_economia < energia_desempenho);
+    }
+
+    #[test]
+    fn test_sono_e_latencia_de_despertar() {
+        let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.habilitar_modelo_energia(Governador::Desempenho);
+        for _ in 0..8 {
+            sistema.escalonar();
+        }
+        assert_eq!(sistema.nucleos[0].estado_energia, EstadoEnergia::Dormindo);
+
+        sistema.adicionar_processo(Processo::new(1, 5, 1));
+        for _ in 0..4 {
+            sistema.escalonar();
+        }
+
+        // Despachado no 1º passo, 2 passos de latência, 1 passo de execução
+        assert_eq!(sistema.nucleos[0].despertares, 1);
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().tempo_restante, 4);
+        assert_eq!(sistema.nucleos[0].estado_energia, EstadoEnergia::Ativo);
+    }
+
+    #[test]
+    fn test_banqueiro_adia_concessao_insegura() {
+        let mut sistema = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.tratamento_deadlock = TratamentoDeadlock::Evitacao;
+        // Cada processo usa 1 impressora agora, mas pode chegar a pedir as 2
+        for id in 1..=2 {
+            sistema.adicionar_processo(Processo::new(id, 5, 1)
+                .necessita_recurso(Recurso::Impressora, 1)
+                .com_demanda_maxima(Recurso::Impressora, 2));
+        }
+
+        sistema.escalonar();
+
+        // Conceder a segunda impressora deixaria ambos sem poder completar a demanda máxima
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
+        assert!(sistema.nucleos[1].processo_atual.is_none());
+        assert_eq!(sistema.processos_bloqueados[0].id, 2);
+        assert_eq!(sistema.estatisticas.pedidos_adiados, 1);
+
+        // Na detecção, a mesma concessão é feita sem verificar o estado seguro
+        let mut deteccao = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+        for id in 1..=2 {
+            deteccao.adicionar_processo(Processo::new(id, 5, 1)
+                .necessita_recurso(Recurso::Impressora, 1)
+                .com_demanda_maxima(Recurso::Impressora, 2));
+        }
+        deteccao.escalonar();
+        assert!(deteccao.nucleos.iter().all(|n| n.processo_atual.is_some()));
+
+        // Quando P1 termina e libera a impressora, P2 é desbloqueado
+        sistema.executar(20);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+    }
+
+    #[test]
+    fn test_banqueiro_rejeita_demanda_acima_da_capacidade() {
+        let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.tratamento_deadlock = TratamentoDeadlock::Evitacao;
+        sistema.adicionar_processo(Processo::new(1, 5, 1).com_demanda_maxima(Recurso::Scanner, 2));
+
+        assert_eq!(sistema.quantidade_prontos(), 0);
+        assert_eq!(sistema.eventos, vec![Evento::ProcessoRejeitado { tempo: 0, processo: 1 }]);
+    }
+
+    #[test]
+    fn test_roteiro_de_recursos_espera_circular() {
+        let mut sistema = crate::examples::exemplo_deadlock();
+
+        for _ in 0..4 {
+            sistema.escalonar();
+        }
+
+        // Cada processo segura um recurso e espera o que o outro possui
+        assert_eq!(sistema.processos_bloqueados.len(), 2);
+        for processo in &sistema.processos_bloqueados {
+            assert_eq!(processo.recursos_alocados.values().sum::<u32>(), 1);
+            assert!(processo.pedido_pendente.is_some());
+        }
+        assert_eq!(sistema.recursos_disponiveis[&Recurso::Impressora], 0);
+        assert_eq!(sistema.recursos_disponiveis[&Recurso::Scanner], 0);
+        let deadlock = sistema.verificar_deadlock().unwrap();
+        assert_eq!(deadlock.processos, vec![1, 2]);
+        assert_eq!(deadlock.ciclos, vec![vec![(1, Recurso::Scanner), (2, Recurso::Impressora)]]);
+    }
+
+    #[test]
+    fn test_roteiro_libera_recurso_durante_execucao() {
+        let mut sistema = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.recursos_disponiveis.insert(Recurso::Scanner, 1);
+        sistema.adicionar_processo(Processo::new(1, 8, 1)
+            .adquire_em(0, Recurso::Scanner, 1)
+            .libera_em(2, Recurso::Scanner, 1));
+        sistema.adicionar_processo(Processo::new(2, 8, 1).adquire_em(1, Recurso::Scanner, 1));
+
+        for _ in 0..3 {
+            sistema.escalonar();
+        }
+        assert_eq!(sistema.processos_bloqueados[0].id, 2);
+
+        // P1 libera o scanner após 2 unidades, antes de terminar, e P2 é desbloqueado
+        for _ in 0..2 {
+            sistema.escalonar();
+        }
+        assert!(sistema.processos_bloqueados.is_empty());
+        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
+        assert_eq!(sistema.nucleos[1].processo_atual.as_ref().unwrap().recursos_alocados[&Recurso::Scanner], 1);
+    }
+
+    #[test]
+    fn test_recuperacao_encerrar_todos() {
+        let mut sistema = crate::examples::exemplo_deadlock();
+        sistema.recuperacao_deadlock = RecuperacaoDeadlock::EncerrarTodos;
+        sistema.executar(30);
+
+        assert_eq!(sistema.estatisticas.deadlocks_detectados, 1);
+        assert_eq!(sistema.processos_abortados.len(), 2);
+        assert!(sistema.processos_abortados.iter().all(|a| a.processo.estado == EstadoProcesso::Abortado));
+        assert_eq!(sistema.recursos_disponiveis[&Recurso::Impressora], 1);
+        assert_eq!(sistema.recursos_disponiveis[&Recurso::Scanner], 1);
+    }
+
+    #[test]
+    fn test_recuperacao_encerra_vitima_de_menor_prioridade() {
+        let mut sistema = crate::examples::exemplo_deadlock();
+        sistema.processos[1].prioridade = 0;
+        sistema.executar(30);
+
+        // Só P2 é abortado; P1 obtém o scanner e termina
+        assert_eq!(sistema.processos_abortados.len(), 1);
+        assert_eq!(sistema.processos_abortados[0].processo.id, 2);
+        assert_eq!(sistema.processos_abortados[0].motivo,
+                   MotivoAborto::VitimaDeadlock { criterio: CriterioVitima::MenorPrioridade, processos: vec![1, 2] });
+        assert_eq!(sistema.estatisticas.processos_concluidos, 1);
+    }
+
+    #[test]
+    fn test_recuperacao_preemptar_e_retroceder() {
+        let mut sistema = crate::examples::exemplo_deadlock();
+        sistema.recuperacao_deadlock = RecuperacaoDeadlock::PreemptarERetroceder(CriterioVitima::MenorProgresso);
+        sistema.executar(60);
+
+        // Ninguém é abortado: a vítima recomeça e ambos terminam
+        assert!(sistema.processos_abortados.is_empty());
+        assert!(sistema.estatisticas.reversoes >= 1);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+    }
+
+    #[test]
+    fn test_retrocesso_ate_checkpoint() {
+        let mut sistema = Sistema::new(2, 20, AlgoritmoEscalonamento::RoundRobin);
+        sistema.recursos_disponiveis.insert(Recurso::Impressora, 1);
+        sistema.recursos_disponiveis.insert(Recurso::Scanner, 1);
+        sistema.recuperacao_deadlock = RecuperacaoDeadlock::PreemptarERetroceder(CriterioVitima::MenorPrioridade);
+        sistema.intervalo_checkpoint = 1;
+        sistema.adicionar_processo(Processo::new(1, 8, 1)
+            .adquire_em(3, Recurso::Impressora, 1)
+            .adquire_em(5, Recurso::Scanner, 1));
+        sistema.adicionar_processo(Processo::new(2, 8, 5)
+            .adquire_em(0, Recurso::Scanner, 1)
+            .adquire_em(4, Recurso::Impressora, 1));
+
+        for _ in 0..11 {
+            sistema.escalonar();
+        }
+
+        // P1 volta ao checkpoint com 3 unidades, anterior à impressora, em vez de recomeçar
+        assert_eq!(sistema.estatisticas.reversoes, 1);
+        assert_eq!(sistema.estatisticas.trabalho_desperdicado, 2);
+        let p1 = sistema.todos_processos().find(|p| p.id == 1).unwrap();
+        assert_eq!(p1.tempo_restante, 5);
+        assert!(p1.recursos_alocados.is_empty());
+
+        sistema.executar(40);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+    }
+
+    #[test]
+    fn test_checkpoints_com_custo_de_migracao() {
+        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
+        sistema.custo_migracao = 2;
+        sistema.intervalo_checkpoint = 3;
+        sistema.adicionar_processo(Processo::new(1, 10, 1).com_nucleo_preferido(1));
+        sistema.escalonar();
+        let mut processo = Processo::new(2, 10, 1).com_nucleo_preferido(1);
+        processo.ultimo_nucleo = Some(1);
+        sistema.adicionar_processo(processo);
+
+        sistema.executar(40);
+        assert!(sistema.estatisticas.migracoes >= 1);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+        // A migração não altera o trabalho: checkpoints com 7, 4 e 1 unidades restantes
+        assert_eq!(sistema.estatisticas.checkpoints, 6);
     }
 
-    fn escolher_proximo_processo(&self) -> Option<&Processo> {
-        match self.algoritmo {
-            AlgoritmoEscalonamento::RoundRobin => self.processos.front(),
-            AlgoritmoEscalonamento::Prioridade => {
-                self.processos.iter().max_by_key(|p| p.prioridade)
-            },
-            AlgoritmoEscalonamento::ShortestJobFirst => {
-                self.processos.iter().min_by_key(|p| p.tempo_restante)
-            },
+    #[test]
+    fn test_prevencao_por_ordem_global() {
+        let mut rejeitar = crate::examples::exemplo_deadlock();
+        rejeitar.processos.clear();
+        rejeitar.tratamento_deadlock = TratamentoDeadlock::Prevencao(ViolacaoOrdem::Rejeitar);
+        let mut reordenar = crate::examples::exemplo_deadlock();
+        reordenar.processos.clear();
+        reordenar.tratamento_deadlock = TratamentoDeadlock::Prevencao(ViolacaoOrdem::Reordenar);
+
+        // Mesma espera circular do exemplo: P2 pede o scanner (posto maior) antes da impressora
+        for sistema in [&mut rejeitar, &mut reordenar] {
+            sistema.adicionar_processo(Processo::new(1, 5, 1)
+                .adquire_em(0, Recurso::Impressora, 1)
+                .adquire_em(2, Recurso::Scanner, 1));
+            sistema.adicionar_processo(Processo::new(2, 5, 1)
+                .adquire_em(0, Recurso::Scanner, 1)
+                .adquire_em(2, Recurso::Impressora, 1));
+            sistema.executar(40);
+            assert_eq!(sistema.estatisticas.violacoes_ordem, 1);
+            assert!(sistema.processos_bloqueados.is_empty());
         }
+
+        assert_eq!(rejeitar.estatisticas.processos_concluidos, 1);
+        assert_eq!(reordenar.estatisticas.processos_concluidos, 2);
     }
 
-    pub fn executar(&mut self, passos: u32) {
-        println!("Iniciando sistema com {} núcleos, algoritmo {}, quantum {}",
-                 self.nucleos.len(), self.algoritmo, self.quantum);
+    #[test]
+    fn test_memoria_fungivel() {
+        let mut sistema = Sistema::new(3, 10, AlgoritmoEscalonamento::RoundRobin);
+        // Pedidos de tamanhos diferentes saem do mesmo pool
+        sistema.adicionar_processo(Processo::new(1, 5, 1).necessita_recurso(Recurso::Memoria, 512));
+        sistema.adicionar_processo(Processo::new(2, 5, 1).necessita_recurso(Recurso::Memoria, 7000));
 
-        for _ in 0..passos {
-            if self.processos.is_empty() && self.processos_bloqueados.is_empty() &&
-                self.nucleos.iter().all(|n| n.processo_atual.is_none()) {
-                println!("Todos os processos foram concluídos!");
-                break;
-            }
+        sistema.escalonar();
 
-            self.escalonar();
-        }
+        assert_eq!(sistema.nucleos.iter().filter(|n| n.processo_atual.is_some()).count(), 2);
+        assert_eq!(sistema.memoria_em_uso(), 7512);
+        assert_eq!(sistema.memoria_livre(), MEMORIA_TOTAL_MB - 7512);
+        assert_eq!(sistema.memoria_total(), MEMORIA_TOTAL_MB);
 
-        println!("Simulação concluída no tempo {}", self.tempo_global);
-        self.mostrar_estatisticas();
+        sistema.adicionar_processo(Processo::new(3, 5, 1).necessita_recurso(Recurso::Memoria, 1024));
+        sistema.escalonar();
+        assert_eq!(sistema.processos_bloqueados[0].id, 3);
     }
 
-    pub fn mostrar_estatisticas(&self) {
-        println!("\n=== Estatísticas ===");
-        for nucleo in &self.nucleos {
-            println!("Núcleo {}: tempo ocioso = {}", nucleo.id, nucleo.tempo_ocioso);
-        }
+    #[test]
+    fn test_falta_de_pagina_bloqueia_processo() {
+        let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.habilitar_paginacao(4, PoliticaSubstituicao::Fifo, 3);
+        sistema.adicionar_processo(Processo::new(1, 4, 1).com_referencias(&[0]));
 
-        println!("\nRecursos disponíveis:");
-        for (recurso, quantidade) in &self.recursos_disponiveis {
-            println!("  {}: {}", recurso, quantidade);
+        sistema.escalonar(); // despacho
+        sistema.escalonar(); // primeira referência: falta
+        assert_eq!(sistema.processos_bloqueados[0].id, 1);
+        assert_eq!(sistema.processos_bloqueados[0].bloqueado_ate, Some(3));
+        assert!(sistema.nucleos[0].processo_atual.is_none());
+
+        sistema.escalonar();
+        sistema.escalonar();
+        assert!(sistema.processos_bloqueados.is_empty());
+
+        for _ in 0..10 {
+            sistema.escalonar();
         }
+        let memoria_virtual = sistema.memoria_virtual.as_ref().unwrap();
+        assert_eq!(sistema.estatisticas.processos_concluidos, 1);
+        assert_eq!(memoria_virtual.acessos, 5);
+        assert_eq!(memoria_virtual.faltas, 1);
+        assert_eq!(memoria_virtual.quadros_ocupados(), 0);
     }
 
-    pub fn mostrar_estatisticas_detalhadas(&self) {
-        println!("\n=== ESTATÍSTICAS DETALHADAS ===");
-        println!("Tempo global: {}", self.tempo_global);
-        println!("Algoritmo: {}", self.algoritmo);
-        println!("Quantum: {}", self.quantum);
-        println!("Taxa de chegada: {} processos/segundo", self.taxa_chegada_processos);
-        
-        println!("\n=== NÚCLEOS ===");
-        for nucleo in &self.nucleos {
-            let status = if nucleo.processo_atual.is_some() {
-                format!("Executando P{}", nucleo.processo_atual.as_ref().unwrap().id)
-            } else {
-                "Ocioso".to_string()
-            };
-            println!("Núcleo {}: {} (tempo ocioso: {})", nucleo.id, status, nucleo.tempo_ocioso);
-        }
-        
-        println!("\n=== FILAS DE PROCESSOS ===");
-        println!("Prontos: {} processos", self.processos.len());
-        for (i, processo) in self.processos.iter().take(5).enumerate() {
-            println!("  {}. P{} (prioridade: {}, tempo restante: {})", 
-                     i+1, processo.id, processo.prioridade, processo.tempo_restante);
-        }
-        if self.processos.len() > 5 {
-            println!("  ... e mais {} processos", self.processos.len() - 5);
-        }
-        
-        println!("Bloqueados: {} processos", self.processos_bloqueados.len());
-        for (i, processo) in self.processos_bloqueados.iter().take(3).enumerate() {
-            println!("  {}. P{} (prioridade: {}, tempo restante: {})", 
-                     i+1, processo.id, processo.prioridade, processo.tempo_restante);
-        }
-        if self.processos_bloqueados.len() > 3 {
-            println!("  ... e mais {} processos", self.processos_bloqueados.len() - 3);
-        }
-        
-        println!("\n=== RECURSOS DISPONÍVEIS ===");
-        for (recurso, quantidade) in &self.recursos_disponiveis {
-            println!("  {}: {}", recurso, quantidade);
+    #[test]
+    fn test_thrashing_com_poucos_quadros() {
+        let mut sistema = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.habilitar_paginacao(2, PoliticaSubstituicao::Lru, 1);
+        sistema.adicionar_processo(Processo::new(1, 30, 1).com_referencias(&[0, 1, 2]));
+        sistema.adicionar_processo(Processo::new(2, 30, 1).com_referencias(&[5, 6, 7]));
+
+        for _ in 0..20 {
+            sistema.escalonar();
         }
+        let memoria_virtual = sistema.memoria_virtual.as_ref().unwrap();
+        // Cada working set sozinho já excede os quadros: toda página nova falta
+        // e só a referência logo após a carga encontra a página
+        assert!(memoria_virtual.em_thrashing());
+        assert!(memoria_virtual.passos_thrashing > 0);
+        assert!(memoria_virtual.taxa_faltas() >= 0.5);
+        assert!(sistema.todos_processos().all(|p| p.faltas_pagina > 0));
     }
 
-    pub fn escalonar_interativo(&mut self) {
-        // Versão simplificada do escalonar para modo interativo
-        self.escalonar();
-    }
-}
+    #[test]
+    fn test_semaforo_usa_fila_propria() {
+        let mut sistema = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+        let semaforo = sistema.criar_semaforo("s", 0);
+        sistema.adicionar_processo(Processo::new(1, 3, 1).espera_em(0, semaforo));
+        sistema.adicionar_processo(Processo::new(2, 3, 1).sinaliza_em(1, semaforo));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        sistema.escalonar();
+        sistema.escalonar();
+        assert_eq!(sistema.objetos_sincronizacao[semaforo].fila[0].id, 1);
+        assert!(sistema.processos_bloqueados.is_empty());
+        assert_eq!(sistema.quantidade_bloqueados(), 1);
+
+        // O V entrega a unidade diretamente a quem espera
+        sistema.escalonar();
+        assert!(sistema.objetos_sincronizacao[semaforo].fila.is_empty());
+        assert_eq!(sistema.objetos_sincronizacao[semaforo].tipo, TipoSincronizacao::Semaforo { valor: 0 });
+
+        sistema.executar(20);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+    }
 
     #[test]
-    fn test_processo_new() {
-        let processo = Processo::new(1, 100, 5);
-        
-        assert_eq!(processo.id, 1);
-        assert_eq!(processo.prioridade, 5);
-        assert_eq!(processo.tempo_total, 100);
-        assert_eq!(processo.tempo_restante, 100);
-        assert_eq!(processo.tempo_chegada, 0);
-        assert_eq!(processo.estado, EstadoProcesso::Pronto);
-        assert!(processo.recursos_alocados.is_empty());
-        assert!(processo.recursos_necessarios.is_empty());
+    fn test_objetos_de_sincronizacao_invalidos_sao_recusados() {
+        let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::RoundRobin);
+        let semaforo = sistema.criar_semaforo("s", 1);
+        let condicao = sistema.criar_condicao("c", semaforo);
+        assert_eq!(sistema.eventos, vec![Evento::CondicaoSemMutex { tempo: 0, condicao, mutex: semaforo }]);
+
+        // lock em um semáforo, P em um id inexistente e wait em condição sem mutex
+        sistema.adicionar_processo(Processo::new(1, 3, 1).trava_em(0, semaforo));
+        sistema.adicionar_processo(Processo::new(2, 3, 1).espera_em(0, 42));
+        sistema.adicionar_processo(Processo::new(3, 3, 1).aguarda_condicao_em(0, condicao));
+        sistema.adicionar_processo(Processo::new(4, 3, 1).espera_em(0, semaforo));
+        assert_eq!(sistema.eventos[1..], [
+            Evento::SincronizacaoInvalida { tempo: 0, processo: 1, operacao: OperacaoSincronizacao::Travar(semaforo) },
+            Evento::SincronizacaoInvalida { tempo: 0, processo: 2, operacao: OperacaoSincronizacao::Esperar(42) },
+            Evento::SincronizacaoInvalida { tempo: 0, processo: 3, operacao: OperacaoSincronizacao::AguardarCondicao(condicao) },
+        ]);
+        assert_eq!(sistema.quantidade_prontos(), 1);
+
+        // Um processo que chegou sem passar pela admissão só tem a operação ignorada
+        sistema.processos.push_back(Processo::new(5, 3, 1).trava_em(1, semaforo));
+        sistema.executar(20);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
     }
 
     #[test]
-    fn test_processo_necessita_recurso() {
-        let processo = Processo::new(1, 100, 5)
-            .necessita_recurso(Recurso::Impressora, 1)
-            .necessita_recurso(Recurso::Memoria(256), 2);
-        
-        assert_eq!(processo.recursos_necessarios.get(&Recurso::Impressora), Some(&1));
-        assert_eq!(processo.recursos_necessarios.get(&Recurso::Memoria(256)), Some(&2));
-        assert_eq!(processo.recursos_necessarios.len(), 2);
+    fn test_condicao_libera_e_readquire_mutex() {
+        let mut sistema = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+        let mutex = sistema.criar_mutex("m");
+        let condicao = sistema.criar_condicao("c", mutex);
+        sistema.adicionar_processo(Processo::new(1, 4, 1)
+            .trava_em(0, mutex).aguarda_condicao_em(0, condicao).destrava_em(2, mutex));
+        sistema.adicionar_processo(Processo::new(2, 4, 1)
+            .trava_em(1, mutex).sinaliza_condicao_em(1, condicao).destrava_em(2, mutex));
+
+        sistema.escalonar();
+        sistema.escalonar();
+        assert_eq!(sistema.objetos_sincronizacao[condicao].fila[0].id, 1);
+        assert_eq!(sistema.objetos_sincronizacao[mutex].tipo, TipoSincronizacao::Mutex { dono: None });
+
+        // Sinalizado, P1 passa a esperar pelo mutex que P2 ainda possui
+        sistema.escalonar();
+        assert!(sistema.objetos_sincronizacao[condicao].fila.is_empty());
+        assert_eq!(sistema.objetos_sincronizacao[mutex].fila[0].id, 1);
+        assert_eq!(sistema.objetos_sincronizacao[mutex].tipo, TipoSincronizacao::Mutex { dono: Some(2) });
+
+        sistema.escalonar();
+        assert_eq!(sistema.objetos_sincronizacao[mutex].tipo, TipoSincronizacao::Mutex { dono: Some(1) });
+
+        sistema.executar(20);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+        assert_eq!(sistema.objetos_sincronizacao[mutex].tipo, TipoSincronizacao::Mutex { dono: None });
     }
 
     #[test]
-    fn test_nucleo_new() {
-        let nucleo = Nucleo::new(0);
-        
-        assert_eq!(nucleo.id, 0);
-        assert!(nucleo.processo_atual.is_none());
-        assert_eq!(nucleo.tempo_ocioso, 0);
+    fn test_cenarios_de_sincronizacao() {
+        let mut sistema = crate::examples::exemplo_produtor_consumidor();
+        sistema.executar(60);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+        assert_eq!(sistema.objetos_sincronizacao[0].tipo, TipoSincronizacao::Semaforo { valor: 2 });
+        assert_eq!(sistema.objetos_sincronizacao[1].tipo, TipoSincronizacao::Semaforo { valor: 0 });
+
+        let mut sistema = crate::examples::exemplo_leitores_escritores();
+        sistema.executar(60);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 5);
+        assert!(sistema.objetos_sincronizacao[0].bloqueios > 0);
+
+        let mut sistema = crate::examples::exemplo_filosofos();
+        sistema.executar(60);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 5);
     }
 
     #[test]
-    fn test_sistema_new() {
-        let sistema = Sistema::new(4, 10, AlgoritmoEscalonamento::RoundRobin);
-        
-        assert_eq!(sistema.nucleos.len(), 4);
-        assert_eq!(sistema.quantum, 10);
-        assert_eq!(sistema.algoritmo, AlgoritmoEscalonamento::RoundRobin);
-        assert_eq!(sistema.tempo_global, 0);
-        assert_eq!(sistema.taxa_chegada_processos, 20);
-        assert!(sistema.processos.is_empty());
-        assert!(sistema.processos_bloqueados.is_empty());
-        
-        // Verificar recursos iniciais
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Impressora), Some(&2));
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Scanner), Some(&1));
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Disco), Some(&3));
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Memoria(1024)), Some(&8));
+    fn test_filosofos_simetricos_travam() {
+        let mut sistema = Sistema::new(5, 10, AlgoritmoEscalonamento::RoundRobin);
+        let garfos: Vec<usize> = (0..5).map(|i| sistema.criar_mutex(&format!("garfo {}", i))).collect();
+        for i in 0..5 {
+            sistema.adicionar_processo(Processo::new(i as u32 + 1, 4, 1)
+                .trava_em(0, garfos[i]).trava_em(1, garfos[(i + 1) % 5])
+                .destrava_em(3, garfos[(i + 1) % 5]).destrava_em(3, garfos[i]));
+        }
+
+        for _ in 0..30 {
+            sistema.escalonar();
+        }
+        assert_eq!(sistema.estatisticas.processos_concluidos, 0);
+        assert_eq!(sistema.quantidade_bloqueados(), 5);
     }
 
     #[test]
-    fn test_adicionar_processo() {
-        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
-        let processo = Processo::new(1, 50, 3);
-        
-        sistema.adicionar_processo(processo);
-        
-        assert_eq!(sistema.processos.len(), 1);
-        assert_eq!(sistema.processos[0].id, 1);
-        assert_eq!(sistema.processos[0].estado, EstadoProcesso::Pronto);
+    fn test_protocolos_evitam_inversao_de_prioridade() {
+        let mut sem_protocolo = crate::examples::exemplo_mars_pathfinder(ProtocoloPrioridade::Nenhum);
+        sem_protocolo.executar(40);
+        assert_eq!(sem_protocolo.estatisticas.deadlines_perdidas, 1);
+        assert!(sem_protocolo.estatisticas.passos_inversao > 0);
+
+        for protocolo in [ProtocoloPrioridade::Heranca, ProtocoloPrioridade::Teto] {
+            let mut sistema = crate::examples::exemplo_mars_pathfinder(protocolo);
+            sistema.executar(40);
+            assert_eq!(sistema.estatisticas.processos_concluidos, 3);
+            assert_eq!(sistema.estatisticas.deadlines_perdidas, 0);
+            assert_eq!(sistema.estatisticas.passos_inversao, 0);
+            assert!(sistema.estatisticas.elevacoes_prioridade > 0);
+            // As chegadas não são jobs periódicos
+            assert_eq!(sistema.estatisticas.jobs_liberados, 0);
+        }
     }
 
     #[test]
-    fn test_escolher_proximo_processo_round_robin() {
-        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::RoundRobin);
-        
-        let processo1 = Processo::new(1, 30, 1);
-        let processo2 = Processo::new(2, 20, 5);
-        let processo3 = Processo::new(3, 40, 3);
-        
-        sistema.adicionar_processo(processo1);
-        sistema.adicionar_processo(processo2);
-        sistema.adicionar_processo(processo3);
-        
-        let proximo = sistema.escolher_proximo_processo();
-        assert_eq!(proximo.unwrap().id, 1); // Primeiro da fila
+    fn test_heranca_eleva_dono_e_restaura_base() {
+        let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::Prioridade);
+        sistema.definir_escalonador(Box::new(EscalonadorPrioridade::preemptivo(0)));
+        sistema.protocolo_prioridade = ProtocoloPrioridade::Heranca;
+        let mutex = sistema.criar_mutex("m");
+        sistema.adicionar_processo(Processo::new(1, 6, 1).trava_em(0, mutex).destrava_em(4, mutex));
+        sistema.adicionar_processo(Processo::new(2, 2, 8).trava_em(0, mutex).destrava_em(1, mutex).com_chegada(2));
+
+        for _ in 0..4 {
+            sistema.escalonar();
+        }
+        let dono = sistema.nucleos[0].processo_atual.as_ref().unwrap();
+        assert_eq!(dono.id, 1);
+        assert_eq!(dono.prioridade, 1);
+        assert_eq!(dono.prioridade_efetiva(), 8);
+
+        // Ao destravar, o mutex passa a P2 e P1 volta à prioridade base
+        sistema.executar(4);
+        assert!(sistema.todos_processos().all(|p| p.prioridade_herdada.is_none()));
     }
 
     #[test]
-    fn test_escolher_proximo_processo_prioridade() {
-        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::Prioridade);
-        
-        let processo1 = Processo::new(1, 30, 1);
-        let processo2 = Processo::new(2, 20, 5);
-        let processo3 = Processo::new(3, 40, 3);
-        
-        sistema.adicionar_processo(processo1);
-        sistema.adicionar_processo(processo2);
-        sistema.adicionar_processo(processo3);
-        
-        let proximo = sistema.escolher_proximo_processo();
-        assert_eq!(proximo.unwrap().id, 2); // Maior prioridade (5)
+    fn test_rajada_de_es_bloqueia_e_acorda() {
+        let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::RoundRobin);
+        let processo = Processo::new(1, 0, 1)
+            .com_rajadas(&[Rajada::Cpu(2), Rajada::Es(Recurso::Disco, 4), Rajada::Cpu(1)]);
+        assert_eq!(processo.tempo_total, 3);
+        assert!(processo.limitado_por_es());
+        sistema.adicionar_processo(processo);
+
+        for _ in 0..4 {
+            sistema.escalonar();
+        }
+        assert_eq!(sistema.processos_bloqueados[0].dispositivo_es, Some(Recurso::Disco));
+        assert_eq!(sistema.processos_bloqueados[0].bloqueado_ate, Some(6));
+
+        sistema.executar(20);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 1);
+        assert_eq!(sistema.estatisticas.operacoes_es, 1);
+        assert_eq!(sistema.estatisticas.passos_es, 4);
+        assert_eq!(sistema.estatisticas.limitados_es.concluidos, 1);
     }
 
     #[test]
-    fn test_escolher_proximo_processo_shortest_job_first() {
-        let mut sistema = Sistema::new(1, 5, AlgoritmoEscalonamento::ShortestJobFirst);
-        
-        let processo1 = Processo::new(1, 30, 1);
-        let processo2 = Processo::new(2, 20, 5);
-        let processo3 = Processo::new(3, 40, 3);
-        
-        sistema.adicionar_processo(processo1);
-        sistema.adicionar_processo(processo2);
-        sistema.adicionar_processo(processo3);
-        
-        let proximo = sistema.escolher_proximo_processo();
-        assert_eq!(proximo.unwrap().id, 2); // Menor tempo restante (20)
+    fn test_es_sobrepoe_cpu_de_outro_processo() {
+        let mut sistema = Sistema::new(1, 2, AlgoritmoEscalonamento::RoundRobin);
+        // Rajada final de E/S: o processo só conclui depois dela
+        sistema.adicionar_processo(Processo::new(1, 0, 1)
+            .com_rajadas(&[Rajada::Cpu(1), Rajada::Es(Recurso::Disco, 6), Rajada::Cpu(1), Rajada::Es(Recurso::Disco, 6)]));
+        sistema.adicionar_processo(Processo::new(2, 8, 1));
+
+        sistema.executar(40);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+        assert_eq!(sistema.estatisticas.operacoes_es, 2);
+        assert_eq!(sistema.estatisticas.limitados_cpu.concluidos, 1);
+        assert_eq!(sistema.estatisticas.limitados_es.concluidos, 1);
+        // Enquanto P1 espera o disco, P2 usa a CPU: o núcleo quase não fica ocioso
+        assert!(sistema.nucleos[0].tempo_ocioso < 6);
     }
 
     #[test]
-    fn test_pode_alocar_recursos_insuficientes() {
-        let sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
-        let processo = Processo::new(1, 50, 3)
-            .necessita_recurso(Recurso::Impressora, 3) // Só tem 2 disponíveis
-            .necessita_recurso(Recurso::Memoria(512), 4);
-        
-        assert!(!sistema.pode_alocar_recursos(&processo));
+    fn test_pedidos_aguardam_na_fila_do_disco() {
+        let mut sistema = Sistema::new(2, 10, AlgoritmoEscalonamento::RoundRobin);
+        sistema.habilitar_disco(AlgoritmoDisco::Fcfs);
+        for id in 1..=2 {
+            sistema.adicionar_processo(Processo::new(id, 0, 1)
+                .com_rajadas(&[Rajada::Cpu(1), Rajada::Es(Recurso::Disco, 2), Rajada::Cpu(1)])
+                .com_cilindros(&[100]));
+        }
+
+        for _ in 0..3 {
+            sistema.escalonar();
+        }
+        // Ambos pediram o disco: um em serviço, o outro na fila, sem prazo definido
+        assert_eq!(sistema.processos_bloqueados.len(), 2);
+        assert!(sistema.processos_bloqueados.iter().all(|p| p.bloqueado_ate.is_none() || p.id == 1));
+        assert_eq!(sistema.dispositivos[&Recurso::Disco].fila.len(), 1);
+
+        sistema.executar(30);
+        let disco = &sistema.dispositivos[&Recurso::Disco];
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+        assert_eq!(disco.atendidos, 2);
+        // Só o primeiro pedido move o cabeçote; o segundo espera o primeiro terminar
+        assert_eq!(disco.distancia_total, 100);
+        assert_eq!(disco.espera_total, 3);
+        assert_eq!(disco.posicao, 100);
     }
 
     #[test]
-    fn test_alocar_recursos_sucesso() {
-        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
-        let processo = Processo::new(1, 50, 3)
-            .necessita_recurso(Recurso::Impressora, 1)
-            .necessita_recurso(Recurso::Memoria(1024), 2);
-        
-        let resultado = sistema.alocar_recursos(&processo);
-        assert!(resultado);
-        
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Impressora), Some(&1));
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Memoria(1024)), Some(&6));
+    fn test_cilindro_padrao_com_id_grande() {
+        let processo = Processo::new(u32::MAX, 1, 1);
+        assert!(processo.cilindro_es(CILINDROS_DISCO) < CILINDROS_DISCO);
     }
 
     #[test]
-    fn test_alocar_recursos_falha() {
-        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
-        let processo = Processo::new(1, 50, 3)
-            .necessita_recurso(Recurso::Impressora, 5) // Mais que disponível
-            .necessita_recurso(Recurso::Memoria(1024), 2);
-        
-        let resultado = sistema.alocar_recursos(&processo);
-        assert!(!resultado);
-        
-        // Recursos devem permanecer inalterados
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Impressora), Some(&2));
-        assert_eq!(sistema.recursos_disponiveis.get(&Recurso::Memoria(1024)), Some(&8));
+    fn test_algoritmos_de_disco_reduzem_busca() {
+        let busca_media = |algoritmo| {
+            let mut sistema = crate::examples::exemplo_disco(algoritmo);
+            sistema.executar(200);
+            assert_eq!(sistema.estatisticas.processos_concluidos, 4);
+            sistema.dispositivos[&Recurso::Disco].busca_media()
+        };
+        let fcfs = busca_media(AlgoritmoDisco::Fcfs);
+        assert!(busca_media(AlgoritmoDisco::Sstf) < fcfs);
+        assert!(busca_media(AlgoritmoDisco::Look) < fcfs);
     }
 
     #[test]
-    fn test_escalonar_multiplos_processos() {
-        let mut sistema = Sistema::new(2, 5, AlgoritmoEscalonamento::RoundRobin);
-        
-        let processo1 = Processo::new(1, 3, 1);
-        let processo2 = Processo::new(2, 2, 2);
-        let processo3 = Processo::new(3, 4, 3);
-        
-        sistema.adicionar_processo(processo1);
-        sistema.adicionar_processo(processo2);
-        sistema.adicionar_processo(processo3);
-        
-        // Primeiro passo: dois processos devem ser atribuídos aos dois núcleos
+    fn test_chegada_futura_aguarda_tempo_global() {
+        let mut sistema = Sistema::new(1, 2, AlgoritmoEscalonamento::RoundRobin);
+        sistema.adicionar_processo(Processo::new(1, 4, 1));
+        sistema.adicionar_processo(Processo::new(2, 2, 1).com_chegada(3));
+        assert_eq!(sistema.chegadas_pendentes.len(), 1);
+        assert_eq!(sistema.quantidade_prontos(), 1);
+
+        for _ in 0..3 {
+            sistema.escalonar();
+        }
+        assert_eq!(sistema.chegadas_pendentes.len(), 1);
         sistema.escalonar();
-        
-        let nucleos_ocupados = sistema.nucleos.iter()
-            .filter(|n| n.processo_atual.is_some())
-            .count();
-        assert_eq!(nucleos_ocupados, 2);
-        assert_eq!(sistema.processos.len(), 1); // Um processo ainda na fila
+        assert!(sistema.chegadas_pendentes.is_empty());
+        assert!(sistema.todos_processos().any(|p| p.id == 2));
+
+        sistema.executar(20);
+        assert_eq!(sistema.estatisticas.processos_concluidos, 2);
+        // P1 conclui em T=7 e P2 em T=8, com retorno contado a partir de T=3
+        assert_eq!(sistema.estatisticas.limitados_cpu.retorno_total, 7 + 5);
     }
 
     #[test]
-    fn test_preempcao_round_robin() {
-        let mut sistema = Sistema::new(1, 2, AlgoritmoEscalonamento::RoundRobin); // Quantum 2
-        
-        let processo1 = Processo::new(1, 5, 1);
-        let processo2 = Processo::new(2, 3, 2);
-        
-        sistema.adicionar_processo(processo1);
-        sistema.adicionar_processo(processo2);
-        
-        // Primeiro processo executa
-        sistema.escalonar();
-        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
-        
-        // Segundo passo - ainda processo 1
+    fn test_teto_considera_chegadas_pendentes() {
+        let mut sistema = Sistema::new(1, 10, AlgoritmoEscalonamento::Prioridade);
+        sistema.protocolo_prioridade = ProtocoloPrioridade::Teto;
+        let mutex = sistema.criar_mutex("m");
+        sistema.adicionar_processo(Processo::new(1, 6, 1).trava_em(0, mutex).destrava_em(4, mutex));
+        sistema.adicionar_processo(Processo::new(2, 2, 9).trava_em(0, mutex).destrava_em(1, mutex).com_chegada(5));
+        assert_eq!(sistema.teto_prioridade(mutex), Some(9));
+
+        // P1 assume o teto ao travar, antes de P2 chegar
         sistema.escalonar();
-        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 1);
-        
-        // Terceiro passo - deve haver preempção (quantum = 2)
         sistema.escalonar();
-        assert_eq!(sistema.nucleos[0].processo_atual.as_ref().unwrap().id, 2);
+        assert_eq!(sistema.chegadas_pendentes.len(), 1);
+        let dono = sistema.nucleos[0].processo_atual.as_ref().unwrap();
+        assert_eq!((dono.id, dono.prioridade_efetiva()), (1, 9));
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_quantum_contado_por_despacho() {
+        let mut sistema = Sistema::new(1, 2, AlgoritmoEscalonamento::RoundRobin);
+        sistema.adicionar_processo(Processo::new(1, 6, 1).com_chegada(1));
+        sistema.adicionar_processo(Processo::new(2, 6, 1).com_chegada(1));
+
+        // Despachado em T=1, P1 executa o quantum inteiro antes de ceder o núcleo
+        for _ in 0..3 {
+            sistema.escalonar();
+        }
+        let atual = sistema.nucleos[0].processo_atual.as_ref().unwrap();
+        assert_eq!((atual.id, atual.tempo_no_quantum), (1, 1));
+        sistema.escalonar();
+        let atual = sistema.nucleos[0].processo_atual.as_ref().unwrap();
+        assert_eq!((atual.id, atual.tempo_no_quantum), (2, 0));
+    }
+}